@@ -16,6 +16,8 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 \******************************************************************************/
 
+use std::{io::ErrorKind, sync::Arc};
+
 use async_hid::HidError;
 use time::error::{ComponentRange, IndeterminateOffset};
 
@@ -27,13 +29,45 @@ pub enum ClientError {
     /// A Ws6in1 protocol error.
     ProtocolError(Error),
     /// An HID system error.
-    HidError(String),
+    HidError(Arc<HidError>),
     /// System time error.
-    TimeError(String),
+    TimeError(Arc<time::Error>),
     /// No matching device was found.
     DeviceNotFound,
 }
 
+impl ClientError {
+    /// Returns true if retrying the failed operation may succeed.
+    /// Protocol errors are classified by [Error::is_transient]. HID errors
+    /// are transient if they are caused by an interrupted or timed out
+    /// I/O operation. Missing devices, missing permissions and all other
+    /// system errors are not transient.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::ProtocolError(e) => e.is_transient(),
+            Self::HidError(e) => match e.as_ref() {
+                HidError::Other(e) => e
+                    .downcast_ref::<std::io::Error>()
+                    .is_some_and(|e| Self::is_transient_io(e.kind())),
+                HidError::Message(_) => false,
+            },
+            Self::TimeError(_) => false,
+            Self::DeviceNotFound => false,
+        }
+    }
+
+    fn is_transient_io(kind: ErrorKind) -> bool {
+        matches!(
+            kind,
+            ErrorKind::Interrupted
+                | ErrorKind::WouldBlock
+                | ErrorKind::TimedOut
+                | ErrorKind::ResourceBusy
+                | ErrorKind::UnexpectedEof
+        )
+    }
+}
+
 impl From<Error> for ClientError {
     fn from(e: Error) -> Self {
         Self::ProtocolError(e)
@@ -42,19 +76,19 @@ impl From<Error> for ClientError {
 
 impl From<HidError> for ClientError {
     fn from(e: HidError) -> Self {
-        Self::HidError(e.to_string())
+        Self::HidError(Arc::new(e))
     }
 }
 
 impl From<IndeterminateOffset> for ClientError {
     fn from(e: IndeterminateOffset) -> Self {
-        Self::TimeError(e.to_string())
+        Self::TimeError(Arc::new(e.into()))
     }
 }
 
 impl From<ComponentRange> for ClientError {
     fn from(e: ComponentRange) -> Self {
-        Self::TimeError(e.to_string())
+        Self::TimeError(Arc::new(e.into()))
     }
 }
 
@@ -69,3 +103,56 @@ impl std::fmt::Display for ClientError {
         }
     }
 }
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ProtocolError(e) => Some(e),
+            Self::HidError(e) => Some(e.as_ref()),
+            Self::TimeError(e) => Some(e.as_ref()),
+            Self::DeviceNotFound => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use super::*;
+
+    #[test]
+    fn test_error_source_chain() {
+        let e = ClientError::from(Error::FragmentDiscarded { idx: 2 });
+        assert!(e.is_transient());
+        assert!(matches!(
+            e.source().and_then(|x| x.downcast_ref::<Error>()),
+            Some(Error::FragmentDiscarded { idx: 2 })
+        ));
+
+        let e = ClientError::from(HidError::message("unplugged"));
+        assert!(!e.is_transient());
+        assert!(e
+            .source()
+            .and_then(|x| x.downcast_ref::<HidError>())
+            .is_some());
+
+        let e = ClientError::from(HidError::from(std::io::Error::from(
+            ErrorKind::TimedOut,
+        )));
+        assert!(e.clone().is_transient());
+
+        let e = ClientError::from(HidError::from(std::io::Error::from(
+            ErrorKind::PermissionDenied,
+        )));
+        assert!(!e.is_transient());
+        assert!(!ClientError::DeviceNotFound.is_transient());
+
+        let e = ClientError::from(IndeterminateOffset);
+        assert!(!e.is_transient());
+        assert!(e
+            .source()
+            .and_then(|x| x.downcast_ref::<time::Error>())
+            .is_some());
+    }
+}
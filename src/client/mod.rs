@@ -21,10 +21,12 @@
 use async_hid::{AsyncHidRead, AsyncHidWrite, Device, HidBackend};
 use byteorder_cursor::Cursor;
 use futures_lite::stream::StreamExt;
-use time::{UtcDateTime, UtcOffset};
+use time::{OffsetDateTime, UtcDateTime, UtcOffset};
 
 use crate::{
-    parser::{Ws6in1Assembler, Ws6in1Data},
+    parser::{
+        Ws6in1Assembler, Ws6in1Data, Ws6in1ParseConfig, Ws6in1ParseWarnings,
+    },
     protocol::{AnyWs6in1Message, Ws6in1Serde, Ws6in1SetDate, Ws6in1SetTime},
 };
 
@@ -33,14 +35,14 @@ pub use error::ClientError;
 
 /// Ws6in1 client instance for communication with devices.
 /// This object holds the connection independent communication state.
-pub struct Ws6in1Client {
+pub struct Ws6in1Client<'a> {
     /// Enumerated device to be opened.
     device: Device,
     /// Packet assembler
-    asm: Ws6in1Assembler,
+    asm: Ws6in1Assembler<'a>,
 }
 
-impl Ws6in1Client {
+impl<'a> Ws6in1Client<'a> {
     const BUFFER_SIZE: usize = 128;
     const USAGE_PAGE: u16 = 0xFF00;
     const USAGE_ID: u16 = 0x0001;
@@ -70,8 +72,20 @@ impl Ws6in1Client {
         })
     }
 
+    /// Sets the configuration used to parse received messages.
+    pub fn set_parse_config(&mut self, config: Ws6in1ParseConfig<'a>) {
+        self.asm = Ws6in1Assembler::with_config(config);
+    }
+
+    /// Returns the warnings found while parsing the last received message.
+    pub fn last_warnings(&self) -> &Ws6in1ParseWarnings {
+        self.asm.warnings()
+    }
+
     /// Opens a connection to the device and reads messages.
     /// The received weather data fragments are assembled and parsed.
+    /// The host time at which the last fragment was received is attached
+    /// to the returned data.
     /// This function has no internal timeout and can take up to 20 seconds.
     pub async fn read_weather_data(
         &mut self,
@@ -81,6 +95,7 @@ impl Ws6in1Client {
 
         loop {
             let len = hid.read_input_report(&mut buffer).await?;
+            let received = OffsetDateTime::now_utc();
             let mut cursor = Cursor::new(&buffer[..len]);
             let data = match AnyWs6in1Message::deserialize(&mut cursor)
                 .map_err(|e| e.to_string())
@@ -104,7 +119,8 @@ impl Ws6in1Client {
                 }
             };
 
-            if let Some(data) = data {
+            if let Some(mut data) = data {
+                data.received = Some(received);
                 return Ok(data);
             }
         }
@@ -20,11 +20,16 @@ use core::{fmt::Debug, prelude::rust_2021::derive};
 
 use byteorder_cursor::BufferTooSmall;
 
+use crate::parser::Field;
+
 /// Errors returned from Ws6in1 protocol processing.
 #[derive(Clone, Debug)]
 pub enum Error {
-    /// The provided buffer is too small.
+    /// The provided buffer is too small to serialize a message into.
     BufferTooSmall(BufferTooSmall),
+    /// The received message of `len` bytes ended before the expected
+    /// `expected` bytes.
+    MessageTruncated { len: usize, expected: usize },
     /// The provided buffer contained unexpected trailing bytes and
     /// was not completely deserialized.
     BufferNotConsumed { trailing: usize },
@@ -36,18 +41,44 @@ pub enum Error {
     UnsupportedOpcode { opcode: u8 },
     /// The payload of a packet exceeds the maximum supported length.
     PayloadTooLarge { len: usize },
-    /// The parsed unexpectedly encountered end of input after this token number.
-    UnexpectedEnd { tpos: usize },
+    /// The message ended at byte offset `offset` while the given field was
+    /// expected.
+    UnexpectedEnd { field: Field, offset: usize },
     /// The message contained a non UTF8 character.
     InvalidCharacter { idx: usize },
-    /// Parsing number from string token with given number failed.
-    InvalidToken { tpos: usize },
-    /// Parser encountered "garbage" characters at the end of the message.
-    GarbageEnd { char: u8 },
+    /// Parsing the given field from the token at `span` failed.
+    InvalidToken {
+        field: Field,
+        token: TokenText,
+        span: Span,
+    },
+    /// Parser encountered "garbage" tokens at the end of the message.
+    GarbageEnd { token: TokenText, span: Span },
     /// A fragment was discarded during message assembly.
     FragmentDiscarded { idx: u8 },
     /// A message exceeded maximum length during assembly.
     MessageTooLarge { len: usize },
+    /// A field required to construct the data was not found in the message.
+    MissingField { field: Field },
+    /// The message layout is invalid for the given field.
+    InvalidLayout { field: Field },
+    /// No message layout matches the token count of the message.
+    UnknownLayout { tokens: usize },
+    /// The token types of the message match none or several of the
+    /// layouts with the given token count.
+    AmbiguousLayout { tokens: usize },
+    /// A local timestamp is out of the supported date range.
+    InvalidTimestamp { timestamp: i64 },
+    /// A local timestamp occurs twice because of a DST transition.
+    AmbiguousLocalTime { timestamp: i64 },
+    /// A local timestamp does not exist because of a DST transition.
+    NonexistentLocalTime { timestamp: i64 },
+    /// The extra sensor channel index is out of range.
+    InvalidChannel { channel: usize },
+    /// The given field contains a physically implausible value.
+    ImplausibleValue { field: Field },
+    /// Latitude or longitude are out of range.
+    InvalidCoordinates { latitude: f32, longitude: f32 },
 }
 
 impl core::fmt::Display for Error {
@@ -56,6 +87,13 @@ impl core::fmt::Display for Error {
             Self::BufferTooSmall(e) => {
                 write!(f, "{e}")
             }
+            Self::MessageTruncated { len, expected } => {
+                write!(
+                    f,
+                    "The received message is truncated. Got {len} bytes, \
+                    expected at least {expected}"
+                )
+            }
             Self::BufferNotConsumed { trailing } => {
                 write!(
                     f,
@@ -78,19 +116,28 @@ impl core::fmt::Display for Error {
                     the supported maximum"
                 )
             }
-            Self::UnexpectedEnd { tpos } => {
-                write!(f, "Unexpected end found after token number {tpos}")
+            Self::UnexpectedEnd { field, offset } => {
+                write!(
+                    f,
+                    "Message ended at byte {offset} while field {field:?} \
+                    was expected",
+                )
             }
             Self::InvalidCharacter { idx } => {
                 write!(f, "Non UTF8 character was found at index {idx}")
             }
-            Self::InvalidToken { tpos } => {
-                write!(f, "Parsing token number {tpos} failed",)
+            Self::InvalidToken { field, token, span } => {
+                write!(
+                    f,
+                    "Parsing field {field:?} from token \"{token}\" \
+                    at bytes {span} failed",
+                )
             }
-            Self::GarbageEnd { char } => {
+            Self::GarbageEnd { token, span } => {
                 write!(
                     f,
-                    "Parser found garbage at end of message: 0x{char:02X}",
+                    "Parser found garbage \"{token}\" at end of message \
+                    at bytes {span}",
                 )
             }
             Self::FragmentDiscarded { idx } => {
@@ -106,6 +153,118 @@ impl core::fmt::Display for Error {
                     the supported maximum",
                 )
             }
+            Self::MissingField { field } => {
+                write!(f, "Required field {field:?} is missing")
+            }
+            Self::InvalidLayout { field } => {
+                write!(f, "Message layout is invalid for field {field:?}")
+            }
+            Self::UnknownLayout { tokens } => {
+                write!(f, "No message layout with {tokens} tokens is known")
+            }
+            Self::AmbiguousLayout { tokens } => {
+                write!(
+                    f,
+                    "Message does not match exactly one layout with \
+                    {tokens} tokens",
+                )
+            }
+            Self::InvalidTimestamp { timestamp } => {
+                write!(f, "Local timestamp {timestamp} is out of range")
+            }
+            Self::AmbiguousLocalTime { timestamp } => {
+                write!(
+                    f,
+                    "Local timestamp {timestamp} is ambiguous in the \
+                    given timezone",
+                )
+            }
+            Self::NonexistentLocalTime { timestamp } => {
+                write!(
+                    f,
+                    "Local timestamp {timestamp} does not exist in the \
+                    given timezone",
+                )
+            }
+            Self::InvalidChannel { channel } => {
+                write!(f, "Extra sensor channel {channel} does not exist")
+            }
+            Self::ImplausibleValue { field } => {
+                write!(f, "Field {field:?} contains an implausible value")
+            }
+            Self::InvalidCoordinates {
+                latitude,
+                longitude,
+            } => {
+                write!(f, "Coordinates {latitude}, {longitude} are invalid")
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+impl Error {
+    /// Returns true if the error was caused by a corrupted or unexpected
+    /// message. Such errors are transient and reading the next message
+    /// may succeed. All other errors are caused by invalid arguments or
+    /// configuration and will occur again on retry.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::MessageTruncated { .. }
+            | Self::BufferNotConsumed { .. }
+            | Self::InvalidMagic { .. }
+            | Self::UnsupportedType { .. }
+            | Self::UnsupportedOpcode { .. }
+            | Self::UnexpectedEnd { .. }
+            | Self::InvalidCharacter { .. }
+            | Self::InvalidToken { .. }
+            | Self::GarbageEnd { .. }
+            | Self::FragmentDiscarded { .. }
+            | Self::MessageTooLarge { .. }
+            | Self::MissingField { .. } => true,
+            Self::BufferTooSmall(_)
+            | Self::PayloadTooLarge { .. }
+            | Self::InvalidLayout { .. }
+            | Self::UnknownLayout { .. }
+            | Self::AmbiguousLayout { .. }
+            | Self::InvalidTimestamp { .. }
+            | Self::AmbiguousLocalTime { .. }
+            | Self::NonexistentLocalTime { .. }
+            | Self::InvalidChannel { .. }
+            | Self::ImplausibleValue { .. }
+            | Self::InvalidCoordinates { .. } => false,
+        }
+    }
+
+    /// Returns the location of the error in the parsed message if known.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::UnexpectedEnd { offset, .. } => Some(Span {
+                start: *offset,
+                end: *offset,
+            }),
+            Self::InvalidToken { span, .. } | Self::GarbageEnd { span, .. } => {
+                Some(*span)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a [Display](core::fmt::Display) adapter which renders this
+    /// error together with the parsed message and a caret marker below
+    /// the offending token.
+    pub fn diagnostic<'a>(&'a self, msg: &'a str) -> Diagnostic<'a> {
+        Diagnostic { error: self, msg }
+    }
+}
+
+impl Error {
+    /// Converts a failed length check on a received buffer.
+    pub(crate) fn truncated(e: BufferTooSmall) -> Self {
+        Self::MessageTruncated {
+            len: e.size,
+            expected: e.expected,
         }
     }
 }
@@ -116,5 +275,116 @@ impl From<BufferTooSmall> for Error {
     }
 }
 
+/// Byte range of a token in a parsed message.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Span {
+    /// Offset of the first byte.
+    pub start: usize,
+    /// Offset after the last byte.
+    pub end: usize,
+}
+
+impl core::fmt::Display for Span {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A copy of an offending message token stored inside an [Error].
+/// Tokens longer than [TokenText::CAPACITY] bytes are truncated.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct TokenText {
+    buffer: [u8; Self::CAPACITY],
+    len: u8,
+}
+
+impl TokenText {
+    /// Maximum amount of stored bytes.
+    pub const CAPACITY: usize = 16;
+
+    /// Copies the given token, truncating it at a character boundary.
+    pub fn new(token: &str) -> Self {
+        let mut len = token.len().min(Self::CAPACITY);
+        while !token.is_char_boundary(len) {
+            len -= 1;
+        }
+
+        let mut buffer = [0; Self::CAPACITY];
+        buffer[..len].copy_from_slice(&token.as_bytes()[..len]);
+
+        Self {
+            buffer,
+            len: len as u8,
+        }
+    }
+
+    /// Returns the stored token.
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buffer[..self.len as usize])
+            .unwrap_or_default()
+    }
+}
+
+impl core::fmt::Debug for TokenText {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl core::fmt::Display for TokenText {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Renders an [Error] with a caret marker below the offending token of
+/// the parsed message.
+///
+/// ```text
+/// Parsing field BaroSea from token "10x7" at bytes 57..61 failed
+/// 3 2020-01-17 17:30 20.4 49 6.0 60 0.0 0.0 0.0 0.0 129 SE 10x7 954 ...
+///                                                          ^^^^
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Diagnostic<'a> {
+    error: &'a Error,
+    msg: &'a str,
+}
+
+impl core::fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.error)?;
+
+        let Some(span) = self.error.span() else {
+            return Ok(());
+        };
+
+        f.write_str("\n")?;
+        for c in self.msg.chars() {
+            let c = if c.is_whitespace() { ' ' } else { c };
+            write!(f, "{c}")?;
+        }
+
+        f.write_str("\n")?;
+        for (idx, _) in self.msg.char_indices() {
+            if idx >= span.start {
+                break;
+            }
+            f.write_str(" ")?;
+        }
+        let width = self
+            .msg
+            .get(span.start..span.end)
+            .map(|x| x.chars().count())
+            .unwrap_or(0)
+            .max(1);
+        for _ in 0..width {
+            f.write_str("^")?;
+        }
+
+        Ok(())
+    }
+}
+
 /// A specialized Result type for Ws6in1 operations.
 pub type Result<T> = core::result::Result<T, Error>;
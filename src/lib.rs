@@ -23,8 +23,12 @@
 pub mod client;
 mod container;
 mod error;
+pub mod meteo;
 pub mod parser;
 pub mod protocol;
+pub mod report;
+pub mod station;
+pub mod units;
 
 pub use container::Ws6in1Container;
-pub use error::{Error, Result};
+pub use error::{Diagnostic, Error, Result, Span, TokenText};
@@ -0,0 +1,247 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use crate::{
+    parser::Ws6in1OutdoorData,
+    units::{Knots, MetersPerSecond},
+};
+
+/// Wind force on the Beaufort scale from 0 (calm) to 12 (hurricane).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Beaufort(u8);
+
+impl Beaufort {
+    /// Lower speed limits of forces 1 to 12 in m/s according to WMO.
+    pub const LIMITS_MPS: [f32; 12] = [
+        0.3, 1.6, 3.4, 5.5, 8.0, 10.8, 13.9, 17.2, 20.8, 24.5, 28.5, 32.7,
+    ];
+    /// Lower speed limits of forces 1 to 12 in knots. The gaps between
+    /// the whole knot ranges of the WMO table are split at their midpoints.
+    pub const LIMITS_KNOTS: [f32; 12] = [
+        0.5, 3.5, 6.5, 10.5, 16.5, 21.5, 27.5, 33.5, 40.5, 47.5, 55.5, 63.5,
+    ];
+
+    const NAMES: [&'static str; 13] = [
+        "Calm",
+        "Light air",
+        "Light breeze",
+        "Gentle breeze",
+        "Moderate breeze",
+        "Fresh breeze",
+        "Strong breeze",
+        "Near gale",
+        "Gale",
+        "Strong gale",
+        "Storm",
+        "Violent storm",
+        "Hurricane force",
+    ];
+    const LAND: [&'static str; 13] = [
+        "Smoke rises vertically",
+        "Smoke drift indicates wind direction, wind vanes still",
+        "Wind felt on face, leaves rustle, wind vanes move",
+        "Leaves and small twigs constantly moving, light flags extended",
+        "Dust and loose paper raised, small branches move",
+        "Small trees in leaf begin to sway",
+        "Large branches move, whistling in wires, umbrellas difficult",
+        "Whole trees in motion, walking against the wind is difficult",
+        "Twigs break off trees, progress generally impeded",
+        "Slight structural damage, chimney pots and slates removed",
+        "Trees uprooted, considerable structural damage",
+        "Widespread damage",
+        "Devastation",
+    ];
+    const SEA: [&'static str; 13] = [
+        "Sea like a mirror",
+        "Ripples with the appearance of scales, no foam crests",
+        "Small wavelets, glassy crests which do not break",
+        "Large wavelets, crests begin to break, scattered white horses",
+        "Small waves becoming longer, fairly frequent white horses",
+        "Moderate longer waves, many white horses, chance of spray",
+        "Large waves, extensive white foam crests, probably some spray",
+        "Sea heaps up, foam from breaking waves blown in streaks",
+        "Moderately high waves, crests break into spindrift",
+        "High waves, dense streaks of foam, crests topple and roll over",
+        "Very high waves with long overhanging crests, sea looks white",
+        "Exceptionally high waves, sea covered with long foam patches",
+        "Air filled with foam and spray, sea completely white",
+    ];
+    /// Typical Douglas sea state in open sea for each force.
+    const DOUGLAS: [u8; 13] = [0, 1, 2, 3, 3, 4, 5, 6, 6, 7, 8, 8, 9];
+
+    /// Returns the given force or [None] if it exceeds 12.
+    pub fn new(force: u8) -> Option<Self> {
+        (force <= 12).then_some(Self(force))
+    }
+
+    /// Classifies a wind speed in m/s.
+    pub fn from_speed(speed: MetersPerSecond) -> Self {
+        let count = Self::LIMITS_MPS.iter().filter(|x| speed.0 >= **x).count();
+        Self(count as u8)
+    }
+
+    /// Classifies a wind speed in knots. The speed is converted to m/s
+    /// first since the knot and m/s tables of the WMO differ slightly.
+    pub fn from_knots(speed: Knots) -> Self {
+        Self::from_speed(speed.into())
+    }
+
+    /// Returns the force number.
+    pub fn force(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the name of the force, e.g. "Fresh breeze".
+    pub fn name(self) -> &'static str {
+        Self::NAMES[self.0 as usize]
+    }
+
+    /// Returns the description of effects on land.
+    pub fn land(self) -> &'static str {
+        Self::LAND[self.0 as usize]
+    }
+
+    /// Returns the description of the sea state.
+    pub fn sea(self) -> &'static str {
+        Self::SEA[self.0 as usize]
+    }
+
+    /// Returns the typical Douglas sea state for this force.
+    pub fn douglas(self) -> Douglas {
+        Douglas(Self::DOUGLAS[self.0 as usize])
+    }
+}
+
+/// Sea state on the Douglas scale from 0 (calm, glassy) to
+/// 9 (phenomenal).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Douglas(u8);
+
+impl Douglas {
+    const NAMES: [&'static str; 10] = [
+        "Calm (glassy)",
+        "Calm (rippled)",
+        "Smooth",
+        "Slight",
+        "Moderate",
+        "Rough",
+        "Very rough",
+        "High",
+        "Very high",
+        "Phenomenal",
+    ];
+    const WAVE_HEIGHTS: [&'static str; 10] = [
+        "0 m",
+        "0 to 0.1 m",
+        "0.1 to 0.5 m",
+        "0.5 to 1.25 m",
+        "1.25 to 2.5 m",
+        "2.5 to 4 m",
+        "4 to 6 m",
+        "6 to 9 m",
+        "9 to 14 m",
+        "over 14 m",
+    ];
+
+    /// Returns the given degree or [None] if it exceeds 9.
+    pub fn new(degree: u8) -> Option<Self> {
+        (degree <= 9).then_some(Self(degree))
+    }
+
+    /// Returns the degree of the sea state.
+    pub fn degree(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the name of the sea state, e.g. "Rough".
+    pub fn name(self) -> &'static str {
+        Self::NAMES[self.0 as usize]
+    }
+
+    /// Returns the wave height range of the sea state.
+    pub fn wave_height(self) -> &'static str {
+        Self::WAVE_HEIGHTS[self.0 as usize]
+    }
+}
+
+impl Ws6in1OutdoorData {
+    /// Returns the Beaufort force of the average wind.
+    pub fn beaufort(&self) -> Beaufort {
+        Beaufort::from_speed(self.wind_actual)
+    }
+
+    /// Returns the Beaufort force of the wind gust.
+    pub fn gust_beaufort(&self) -> Beaufort {
+        Beaufort::from_speed(self.wind_gust)
+    }
+
+    /// Returns the ratio of gust to average wind speed as measure of
+    /// turbulence or [None] if calm.
+    pub fn gust_factor(&self) -> Option<f32> {
+        (self.wind_actual.0 >= Beaufort::LIMITS_MPS[0])
+            .then(|| self.wind_gust.0 / self.wind_actual.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_beaufort_classification() {
+        assert_eq!(0, Beaufort::from_speed(MetersPerSecond(0.2)).force());
+        assert_eq!(1, Beaufort::from_speed(MetersPerSecond(0.3)).force());
+        assert_eq!(1, Beaufort::from_speed(MetersPerSecond(1.55)).force());
+        assert_eq!(5, Beaufort::from_speed(MetersPerSecond(8.0)).force());
+        assert_eq!(12, Beaufort::from_speed(MetersPerSecond(40.0)).force());
+        assert_eq!(7, Beaufort::from_knots(Knots(30.0)).force());
+        assert_eq!(
+            Beaufort::from_knots(Knots::from(MetersPerSecond(12.0))),
+            Beaufort::from_speed(MetersPerSecond(12.0))
+        );
+        assert_eq!(
+            Beaufort::from_knots(Knots::from(MetersPerSecond(1.8))),
+            Beaufort::from_speed(MetersPerSecond(1.8))
+        );
+        assert_eq!(None, Beaufort::new(13));
+    }
+
+    #[test]
+    fn test_beaufort_descriptions() {
+        let force = Beaufort::from_speed(MetersPerSecond(9.0));
+        assert_eq!("Fresh breeze", force.name());
+        assert_eq!("Small trees in leaf begin to sway", force.land());
+        assert_eq!("Moderate", force.douglas().name());
+        assert_eq!("1.25 to 2.5 m", force.douglas().wave_height());
+    }
+
+    #[test]
+    fn test_gust_factor() {
+        let mut outdoor = Ws6in1OutdoorData {
+            wind_actual: MetersPerSecond(4.0),
+            wind_gust: MetersPerSecond(6.0),
+            ..Default::default()
+        };
+        assert_eq!(Some(1.5), outdoor.gust_factor());
+        assert_eq!(3, outdoor.beaufort().force());
+        assert_eq!(4, outdoor.gust_beaufort().force());
+
+        outdoor.wind_actual = MetersPerSecond(0.0);
+        assert_eq!(None, outdoor.gust_factor());
+    }
+}
@@ -0,0 +1,305 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use super::vapour_pressure;
+use crate::{
+    parser::Ws6in1OutdoorData,
+    units::{Celsius, Fahrenheit, KilometersPerHour, MetersPerSecond, Percent},
+};
+
+/// Formula used to calculate a [FeelsLike] temperature.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FeelsLikeFormula {
+    /// No adjustment, the air temperature is used.
+    AirTemperature,
+    /// NWS simple heat index formula by Steadman.
+    HeatIndexSimple,
+    /// NWS Rothfusz heat index regression.
+    HeatIndexRothfusz,
+    /// Rothfusz regression with the NWS low humidity adjustment.
+    HeatIndexLowHumidity,
+    /// Rothfusz regression with the NWS high humidity adjustment.
+    HeatIndexHighHumidity,
+    /// NWS / Environment Canada wind chill index of 2001.
+    WindChill,
+    /// Environment Canada humidex.
+    Humidex,
+    /// Australian Bureau of Meteorology apparent temperature
+    /// (Steadman 1994, without radiation).
+    ApparentTemperature,
+}
+
+/// A perceived temperature together with the formula used.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeelsLike {
+    /// The perceived temperature.
+    pub temperature: Celsius,
+    /// Formula used to calculate the temperature.
+    pub formula: FeelsLikeFormula,
+}
+
+impl FeelsLike {
+    /// Minimum air temperature for the heat index (80 °F).
+    pub const HEAT_INDEX_MIN: Celsius = Celsius((80.0 - 32.0) / 1.8);
+    /// Maximum air temperature for the wind chill index.
+    pub const WIND_CHILL_MAX: Celsius = Celsius(10.0);
+    /// Minimum wind speed for the wind chill index (4.8 km/h).
+    pub const WIND_CHILL_MIN_SPEED: MetersPerSecond =
+        MetersPerSecond(4.8 / 3.6);
+    /// Minimum air temperature for the humidex.
+    pub const HUMIDEX_MIN: Celsius = Celsius(20.0);
+    /// Valid air temperature range of the apparent temperature.
+    pub const APPARENT_RANGE: (Celsius, Celsius) =
+        (Celsius(-40.0), Celsius(50.0));
+
+    fn new(temperature: f32, formula: FeelsLikeFormula) -> Self {
+        Self {
+            temperature: Celsius(temperature),
+            formula,
+        }
+    }
+
+    /// Calculates the NWS heat index. Returns [None] below 80 °F.
+    pub fn heat_index(temperature: Celsius, humidity: Percent) -> Option<Self> {
+        if temperature < Self::HEAT_INDEX_MIN {
+            return None;
+        }
+
+        let t = Fahrenheit::from(temperature).0;
+        let rh = f32::from(humidity.0);
+        let simple = 0.5 * (t + 61.0 + (t - 68.0) * 1.2 + rh * 0.094);
+        if (simple + t) / 2.0 < 80.0 {
+            return Some(Self::fahrenheit(
+                simple,
+                FeelsLikeFormula::HeatIndexSimple,
+            ));
+        }
+
+        let mut hi = -42.379 + 2.049_015_2 * t + 10.143_331 * rh
+            - 0.224_755_4 * t * rh
+            - 6.837_83e-3 * t * t
+            - 5.481_717e-2 * rh * rh
+            + 1.228_74e-3 * t * t * rh
+            + 8.528_2e-4 * t * rh * rh
+            - 1.99e-6 * t * t * rh * rh;
+        let mut formula = FeelsLikeFormula::HeatIndexRothfusz;
+
+        if rh < 13.0 && (80.0..=112.0).contains(&t) {
+            hi -= (13.0 - rh) / 4.0
+                * libm::sqrtf((17.0 - libm::fabsf(t - 95.0)) / 17.0);
+            formula = FeelsLikeFormula::HeatIndexLowHumidity;
+        } else if rh > 85.0 && (80.0..=87.0).contains(&t) {
+            hi += (rh - 85.0) / 10.0 * ((87.0 - t) / 5.0);
+            formula = FeelsLikeFormula::HeatIndexHighHumidity;
+        }
+
+        Some(Self::fahrenheit(hi, formula))
+    }
+
+    /// Calculates the wind chill index. Returns [None] above 10 °C or
+    /// for wind speeds up to 4.8 km/h.
+    pub fn wind_chill(
+        temperature: Celsius,
+        wind: MetersPerSecond,
+    ) -> Option<Self> {
+        if temperature > Self::WIND_CHILL_MAX
+            || wind <= Self::WIND_CHILL_MIN_SPEED
+        {
+            return None;
+        }
+
+        let t = temperature.0;
+        let v = libm::powf(KilometersPerHour::from(wind).0, 0.16);
+        Some(Self::new(
+            13.12 + 0.6215 * t - 11.37 * v + 0.3965 * t * v,
+            FeelsLikeFormula::WindChill,
+        ))
+    }
+
+    /// Calculates the humidex. Returns [None] below 20 °C or if it does
+    /// not exceed the air temperature.
+    pub fn humidex(temperature: Celsius, humidity: Percent) -> Option<Self> {
+        let e = vapour_pressure(temperature, humidity).0;
+        if temperature < Self::HUMIDEX_MIN || e <= 10.0 {
+            return None;
+        }
+
+        Some(Self::new(
+            temperature.0 + 0.5555 * (e - 10.0),
+            FeelsLikeFormula::Humidex,
+        ))
+    }
+
+    /// Calculates the apparent temperature in the shade. Returns [None]
+    /// outside of -40 to 50 °C.
+    pub fn apparent_temperature(
+        temperature: Celsius,
+        humidity: Percent,
+        wind: MetersPerSecond,
+    ) -> Option<Self> {
+        let (min, max) = Self::APPARENT_RANGE;
+        if temperature < min || temperature > max || wind.0 < 0.0 {
+            return None;
+        }
+
+        let e = vapour_pressure(temperature, humidity).0;
+        Some(Self::new(
+            temperature.0 + 0.33 * e - 0.70 * wind.0 - 4.00,
+            FeelsLikeFormula::ApparentTemperature,
+        ))
+    }
+
+    fn fahrenheit(value: f32, formula: FeelsLikeFormula) -> Self {
+        Self {
+            temperature: Celsius::from(Fahrenheit(value)),
+            formula,
+        }
+    }
+}
+
+impl Ws6in1OutdoorData {
+    /// Returns the perceived temperature following NWS practice:
+    /// wind chill in cold, heat index in warm conditions and the air
+    /// temperature otherwise.
+    pub fn feels_like(&self) -> FeelsLike {
+        FeelsLike::wind_chill(self.temperature, self.wind_actual)
+            .or_else(|| FeelsLike::heat_index(self.temperature, self.humidity))
+            .unwrap_or(FeelsLike {
+                temperature: self.temperature,
+                formula: FeelsLikeFormula::AirTemperature,
+            })
+    }
+
+    /// Returns the NWS heat index if applicable.
+    pub fn heat_index(&self) -> Option<FeelsLike> {
+        FeelsLike::heat_index(self.temperature, self.humidity)
+    }
+
+    /// Returns the wind chill index if applicable.
+    pub fn wind_chill(&self) -> Option<FeelsLike> {
+        FeelsLike::wind_chill(self.temperature, self.wind_actual)
+    }
+
+    /// Returns the humidex if applicable.
+    pub fn humidex(&self) -> Option<FeelsLike> {
+        FeelsLike::humidex(self.temperature, self.humidity)
+    }
+
+    /// Returns the apparent temperature if applicable.
+    pub fn apparent_temperature(&self) -> Option<FeelsLike> {
+        FeelsLike::apparent_temperature(
+            self.temperature,
+            self.humidity,
+            self.wind_actual,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_feels_like(
+        expected: f32,
+        formula: FeelsLikeFormula,
+        actual: Option<FeelsLike>,
+    ) {
+        let actual = actual.unwrap();
+        assert_eq!(formula, actual.formula);
+        assert!(
+            (expected - actual.temperature.0).abs() < 0.05,
+            "expected {expected}, got {}",
+            actual.temperature.0
+        );
+    }
+
+    #[test]
+    fn test_heat_index() {
+        assert_feels_like(
+            34.78,
+            FeelsLikeFormula::HeatIndexRothfusz,
+            FeelsLike::heat_index(Celsius(32.22), Percent(50)),
+        );
+        assert_eq!(
+            FeelsLikeFormula::HeatIndexLowHumidity,
+            FeelsLike::heat_index(Celsius(38.0), Percent(5))
+                .unwrap()
+                .formula
+        );
+        assert_eq!(
+            FeelsLikeFormula::HeatIndexSimple,
+            FeelsLike::heat_index(Celsius(27.0), Percent(10))
+                .unwrap()
+                .formula
+        );
+        assert_eq!(None, FeelsLike::heat_index(Celsius(25.0), Percent(50)));
+    }
+
+    #[test]
+    fn test_wind_chill() {
+        assert_feels_like(
+            -17.86,
+            FeelsLikeFormula::WindChill,
+            FeelsLike::wind_chill(Celsius(-10.0), MetersPerSecond(20.0 / 3.6)),
+        );
+        assert_eq!(
+            None,
+            FeelsLike::wind_chill(Celsius(12.0), MetersPerSecond(5.0))
+        );
+        assert_eq!(
+            None,
+            FeelsLike::wind_chill(Celsius(-5.0), MetersPerSecond(1.0))
+        );
+    }
+
+    #[test]
+    fn test_humidex_and_apparent_temperature() {
+        assert_feels_like(
+            38.56,
+            FeelsLikeFormula::Humidex,
+            FeelsLike::humidex(Celsius(30.0), Percent(60)),
+        );
+        assert_eq!(None, FeelsLike::humidex(Celsius(15.0), Percent(90)));
+        assert_feels_like(
+            32.28,
+            FeelsLikeFormula::ApparentTemperature,
+            FeelsLike::apparent_temperature(
+                Celsius(30.0),
+                Percent(60),
+                MetersPerSecond(3.0),
+            ),
+        );
+    }
+
+    #[test]
+    fn test_outdoor_feels_like() {
+        let mut outdoor = Ws6in1OutdoorData {
+            temperature: Celsius(18.0),
+            humidity: Percent(50),
+            wind_actual: MetersPerSecond(3.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            FeelsLikeFormula::AirTemperature,
+            outdoor.feels_like().formula
+        );
+
+        outdoor.temperature = Celsius(2.0);
+        assert_eq!(FeelsLikeFormula::WindChill, outdoor.feels_like().formula);
+    }
+}
@@ -0,0 +1,169 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use crate::{
+    parser::{Ws6in1ExtData, Ws6in1IndoorData},
+    units::{Celsius, GramsPerCubicMeter, Hectopascal, Percent},
+};
+
+/// Coefficients of the Magnus formula
+/// `E = c0 * exp(c1 * t / (c2 + t))` for saturation vapour pressure.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Magnus {
+    /// Saturation vapour pressure at 0 °C in hPa.
+    pub c0: f32,
+    /// Dimensionless coefficient.
+    pub c1: f32,
+    /// Temperature coefficient in °C.
+    pub c2: f32,
+}
+
+impl Magnus {
+    /// Over plane water surfaces, valid from -45 to 60 °C
+    /// (WMO-No. 8, Sonntag 1990).
+    pub const WATER: Self = Self {
+        c0: 6.112,
+        c1: 17.62,
+        c2: 243.12,
+    };
+    /// Over plane ice surfaces, valid from -65 to 0.01 °C
+    /// (WMO-No. 8, Sonntag 1990).
+    pub const ICE: Self = Self {
+        c0: 6.112,
+        c1: 22.46,
+        c2: 272.62,
+    };
+
+    /// Returns the saturation vapour pressure at the given temperature.
+    pub fn saturation_vapour_pressure(
+        &self,
+        temperature: Celsius,
+    ) -> Hectopascal {
+        let t = temperature.0;
+        Hectopascal(self.c0 * libm::expf(self.c1 * t / (self.c2 + t)))
+    }
+
+    /// Returns the temperature at which the given vapour pressure
+    /// saturates, or [None] for non-positive pressures.
+    pub fn saturation_temperature(
+        &self,
+        vapour_pressure: Hectopascal,
+    ) -> Option<Celsius> {
+        if vapour_pressure.0 <= 0.0 {
+            return None;
+        }
+
+        let x = libm::logf(vapour_pressure.0 / self.c0);
+        Some(Celsius(self.c2 * x / (self.c1 - x)))
+    }
+}
+
+/// Partial pressure of water vapour in air, relative to water.
+pub fn vapour_pressure(temperature: Celsius, humidity: Percent) -> Hectopascal {
+    let saturation = Magnus::WATER.saturation_vapour_pressure(temperature).0;
+    Hectopascal(saturation * f32::from(humidity.0) / 100.0)
+}
+
+/// Temperature to which air must be cooled to saturate over water.
+/// Returns [None] at 0 % humidity.
+pub fn dew_point(temperature: Celsius, humidity: Percent) -> Option<Celsius> {
+    Magnus::WATER.saturation_temperature(vapour_pressure(temperature, humidity))
+}
+
+/// Temperature to which air must be cooled to saturate over ice.
+/// Returns [None] at 0 % humidity.
+pub fn frost_point(temperature: Celsius, humidity: Percent) -> Option<Celsius> {
+    Magnus::ICE.saturation_temperature(vapour_pressure(temperature, humidity))
+}
+
+/// Mass of water vapour per volume of air.
+pub fn absolute_humidity(
+    temperature: Celsius,
+    humidity: Percent,
+) -> GramsPerCubicMeter {
+    /// 1000 g/kg * 100 Pa/hPa / specific gas constant of water vapour.
+    const FACTOR: f32 = 1e5 / 461.5;
+    let e = vapour_pressure(temperature, humidity).0;
+    GramsPerCubicMeter(FACTOR * e / (temperature.0 + 273.15))
+}
+
+macro_rules! humidity_methods {
+    ($name:ident) => {
+        impl $name {
+            /// Returns the dew point, see [dew_point].
+            pub fn dew_point(&self) -> Option<Celsius> {
+                dew_point(self.temperature, self.humidity)
+            }
+
+            /// Returns the frost point, see [frost_point].
+            pub fn frost_point(&self) -> Option<Celsius> {
+                frost_point(self.temperature, self.humidity)
+            }
+
+            /// Returns the absolute humidity, see [absolute_humidity].
+            pub fn absolute_humidity(&self) -> GramsPerCubicMeter {
+                absolute_humidity(self.temperature, self.humidity)
+            }
+
+            /// Returns the vapour pressure, see [vapour_pressure].
+            pub fn vapour_pressure(&self) -> Hectopascal {
+                vapour_pressure(self.temperature, self.humidity)
+            }
+        }
+    };
+}
+
+humidity_methods!(Ws6in1IndoorData);
+humidity_methods!(Ws6in1ExtData);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 0.01,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_indoor_humidity() {
+        let indoor = Ws6in1IndoorData {
+            temperature: Celsius(20.0),
+            humidity: Percent(50),
+            ..Default::default()
+        };
+
+        assert_close(11.663, indoor.vapour_pressure().0);
+        assert_close(9.255, indoor.dew_point().unwrap().0);
+        assert_close(8.621, indoor.absolute_humidity().0);
+    }
+
+    #[test]
+    fn test_ext_frost_point() {
+        let ext = Ws6in1ExtData {
+            temperature: Celsius(-5.0),
+            humidity: Percent(80),
+        };
+
+        assert_close(-7.917, ext.dew_point().unwrap().0);
+        assert_close(-7.014, ext.frost_point().unwrap().0);
+        assert_eq!(None, dew_point(Celsius(10.0), Percent(0)));
+    }
+}
@@ -0,0 +1,64 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+//! Derived meteorological quantities.
+//!
+//! All calculations use the canonical units of [crate::units] and work
+//! without `std`.
+
+use crate::parser::Ws6in1Data;
+
+mod beaufort;
+mod feels_like;
+mod humidity;
+mod pressure;
+mod rain;
+mod rain_rate;
+mod tendency;
+mod uv;
+mod wind;
+mod zambretti;
+
+pub use beaufort::{Beaufort, Douglas};
+pub use feels_like::{FeelsLike, FeelsLikeFormula};
+pub use humidity::{
+    absolute_humidity, dew_point, frost_point, vapour_pressure, Magnus,
+};
+pub use pressure::{
+    altimeter_setting, qfe, qnh, sea_level_pressure, BaroSeaCheck,
+};
+pub use rain::{
+    RainAccumulator, RainAccumulatorConfig, RainAccumulatorState, RainTotals,
+    StormEvent,
+};
+pub use rain_rate::{
+    RainIntensity, RainRate, RainRateConfig, RainRateEstimator,
+};
+pub use tendency::{PressureTendency, PressureTendencyTracker, PressureTrend};
+pub use uv::{SkinType, UvCategory, UvDailyMax, UvMaximum};
+pub use wind::{PeakGust, WindAggregator, WindAverage, WindRose};
+pub use zambretti::{Hemisphere, Zambretti, ZambrettiTrend};
+
+/// Returns the millisecond timestamp of a reading based on the host receive
+/// time if available and the console time otherwise.
+fn timestamp_ms(data: &Ws6in1Data) -> i64 {
+    match data.received {
+        Some(received) => (received.unix_timestamp_nanos() / 1_000_000) as i64,
+        None => data.local_timestamp * 1000,
+    }
+}
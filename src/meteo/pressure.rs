@@ -0,0 +1,215 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use super::vapour_pressure;
+use crate::{
+    parser::Ws6in1Data,
+    station::StationInfo,
+    units::{Celsius, Hectopascal, Meters, Percent},
+};
+
+/// Standard gravity in m/s².
+const GRAVITY: f32 = 9.806_65;
+/// Specific gas constant of dry air in J/(kg K).
+const GAS_CONSTANT: f32 = 287.05;
+/// Temperature lapse rate of the standard atmosphere in K/m.
+const LAPSE_RATE: f32 = 0.0065;
+/// Sea level temperature of the standard atmosphere in K.
+const ISA_TEMPERATURE: f32 = 288.15;
+/// Exponent of the barometric formula in the standard atmosphere.
+const ISA_EXPONENT: f32 = GRAVITY / (GAS_CONSTANT * LAPSE_RATE);
+
+/// Reduces station pressure to sea level with the hypsometric equation
+/// as recommended by WMO-No. 8.
+///
+/// The mean temperature of the fictitious air column is derived from the
+/// station temperature and the standard lapse rate. If a humidity is
+/// given, the column is corrected by 0.12 K/hPa of vapour pressure.
+pub fn sea_level_pressure(
+    station_pressure: Hectopascal,
+    altitude: Meters,
+    temperature: Celsius,
+    humidity: Option<Percent>,
+) -> Hectopascal {
+    let h = altitude.0;
+    let humidity_correction =
+        humidity.map_or(0.0, |x| 0.12 * vapour_pressure(temperature, x).0);
+    let column_temperature =
+        temperature.0 + 273.15 + LAPSE_RATE * h / 2.0 + humidity_correction;
+
+    Hectopascal(
+        station_pressure.0
+            * libm::expf(GRAVITY * h / (GAS_CONSTANT * column_temperature)),
+    )
+}
+
+/// Reduces station pressure to sea level using the ICAO standard
+/// atmosphere, independent of the actual temperature.
+pub fn qnh(station_pressure: Hectopascal, altitude: Meters) -> Hectopascal {
+    let ratio = 1.0 - LAPSE_RATE * altitude.0 / ISA_TEMPERATURE;
+    Hectopascal(station_pressure.0 * libm::powf(ratio, -ISA_EXPONENT))
+}
+
+/// Converts station pressure into the pressure at another altitude,
+/// e.g. an aerodrome reference point, using the ICAO standard atmosphere.
+pub fn qfe(
+    station_pressure: Hectopascal,
+    altitude: Meters,
+    reference: Meters,
+) -> Hectopascal {
+    let ratio = (ISA_TEMPERATURE - LAPSE_RATE * reference.0)
+        / (ISA_TEMPERATURE - LAPSE_RATE * altitude.0);
+    Hectopascal(station_pressure.0 * libm::powf(ratio, ISA_EXPONENT))
+}
+
+/// Calculates the altimeter setting according to the NWS ASOS algorithm.
+pub fn altimeter_setting(
+    station_pressure: Hectopascal,
+    altitude: Meters,
+) -> Hectopascal {
+    const N: f32 = 0.190_284;
+    let p = station_pressure.0 - 0.3;
+    let k = libm::powf(1013.25, N) * LAPSE_RATE / 288.0;
+
+    Hectopascal(
+        p * libm::powf(1.0 + k * altitude.0 / libm::powf(p, N), 1.0 / N),
+    )
+}
+
+/// Comparison of the console sea level pressure with a computed one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BaroSeaCheck {
+    /// Sea level pressure reported by the console.
+    pub reported: Hectopascal,
+    /// Sea level pressure computed from the absolute pressure.
+    pub computed: Hectopascal,
+    /// True if both differ by more than the given threshold.
+    pub disagrees: bool,
+}
+
+impl BaroSeaCheck {
+    /// Returns the reported minus the computed pressure.
+    pub fn difference(&self) -> Hectopascal {
+        Hectopascal(self.reported.0 - self.computed.0)
+    }
+}
+
+impl Ws6in1Data {
+    /// Reduces the absolute pressure to sea level using the outdoor
+    /// temperature and humidity. Returns [None] if the outdoor sensor is
+    /// unavailable.
+    pub fn sea_level_pressure(
+        &self,
+        station: &StationInfo,
+    ) -> Option<Hectopascal> {
+        let outdoor = self.outdoor.as_ref()?;
+        Some(sea_level_pressure(
+            self.indoor.baro_absolute,
+            station.altitude,
+            outdoor.temperature,
+            Some(outdoor.humidity),
+        ))
+    }
+
+    /// Returns the QNH for the station altitude.
+    pub fn qnh(&self, station: &StationInfo) -> Hectopascal {
+        qnh(self.indoor.baro_absolute, station.altitude)
+    }
+
+    /// Returns the QFE at the given reference altitude.
+    pub fn qfe(&self, station: &StationInfo, reference: Meters) -> Hectopascal {
+        qfe(self.indoor.baro_absolute, station.altitude, reference)
+    }
+
+    /// Returns the altimeter setting for the station altitude.
+    pub fn altimeter_setting(&self, station: &StationInfo) -> Hectopascal {
+        altimeter_setting(self.indoor.baro_absolute, station.altitude)
+    }
+
+    /// Compares the console sea level pressure with
+    /// [Ws6in1Data::sea_level_pressure]. Returns [None] if the outdoor
+    /// sensor is unavailable.
+    pub fn check_baro_sea(
+        &self,
+        station: &StationInfo,
+        threshold: Hectopascal,
+    ) -> Option<BaroSeaCheck> {
+        let computed = self.sea_level_pressure(station)?;
+        let reported = self.indoor.baro_sea;
+
+        Some(BaroSeaCheck {
+            reported,
+            computed,
+            disagrees: libm::fabsf(reported.0 - computed.0) > threshold.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Ws6in1OutdoorData;
+
+    fn assert_close(expected: f32, actual: Hectopascal) {
+        assert!(
+            (expected - actual.0).abs() < 0.05,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    fn data(baro_sea: f32) -> Ws6in1Data {
+        Ws6in1Data::builder()
+            .pressure(Hectopascal(baro_sea), Hectopascal(950.0))
+            .outdoor(Ws6in1OutdoorData {
+                temperature: Celsius(10.0),
+                humidity: Percent(70),
+                ..Default::default()
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_pressure_reduction() {
+        let station = StationInfo::new(48.14, 11.58, Meters(519.0)).unwrap();
+        let data = data(1010.0);
+
+        assert_close(1010.79, data.sea_level_pressure(&station).unwrap());
+        assert_close(1010.66, data.qnh(&station));
+        assert_close(1010.40, data.altimeter_setting(&station));
+        assert_close(952.17, data.qfe(&station, Meters(500.0)));
+        assert_close(950.0, data.qfe(&station, Meters(519.0)));
+    }
+
+    #[test]
+    fn test_check_baro_sea() {
+        let station = StationInfo::new(48.14, 11.58, Meters(519.0)).unwrap();
+
+        let check = data(1010.0).check_baro_sea(&station, Hectopascal(1.5));
+        assert!(!check.unwrap().disagrees);
+
+        let check = data(1016.0).check_baro_sea(&station, Hectopascal(1.5));
+        let check = check.unwrap();
+        assert!(check.disagrees);
+        assert_close(5.21, check.difference());
+
+        let mut data = data(1010.0);
+        data.outdoor = None;
+        assert_eq!(None, data.check_baro_sea(&station, Hectopascal(1.5)));
+    }
+}
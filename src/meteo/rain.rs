@@ -0,0 +1,436 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use time::{Duration, OffsetDateTime};
+
+use crate::{
+    parser::{rain_increment, DstPolicy, Ws6in1Data},
+    station::StationTimezone,
+    units::Millimeters,
+    Result,
+};
+
+/// Configuration of a [RainAccumulator].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RainAccumulatorConfig {
+    /// Timezone of the console clock.
+    pub console: StationTimezone,
+    /// Timezone defining midnight, month and year boundaries of totals.
+    pub reporting: StationTimezone,
+    /// Strategy for ambiguous console times.
+    pub dst_policy: DstPolicy,
+    /// Dry period after which a storm event ends.
+    pub storm_gap: Duration,
+}
+
+impl Default for RainAccumulatorConfig {
+    fn default() -> Self {
+        Self {
+            console: StationTimezone::default(),
+            reporting: StationTimezone::default(),
+            dst_policy: DstPolicy::default(),
+            storm_gap: Duration::DAY,
+        }
+    }
+}
+
+/// Rain amount within a time slot.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+struct RainBucket {
+    slot: i64,
+    amount: f32,
+}
+
+impl RainBucket {
+    fn add<const N: usize>(buckets: &mut [Self; N], slot: i64, amount: f32) {
+        let bucket = &mut buckets[slot.rem_euclid(N as i64) as usize];
+        if bucket.slot != slot {
+            *bucket = Self { slot, amount: 0.0 };
+        }
+        bucket.amount += amount;
+    }
+
+    /// Distributes `amount` evenly over the time range `start..end` in
+    /// seconds using slots of `len` seconds. Slots older than the
+    /// capacity of `buckets` are skipped.
+    fn spread<const N: usize>(
+        buckets: &mut [Self; N],
+        len: i64,
+        start: i64,
+        end: i64,
+        amount: f32,
+    ) {
+        if end <= start {
+            Self::add(buckets, end.div_euclid(len), amount);
+            return;
+        }
+
+        let rate = amount / (end - start) as f32;
+        let last = (end - 1).div_euclid(len);
+        let first = start.div_euclid(len).max(last - N as i64 + 1);
+        for slot in first..=last {
+            let from = (slot * len).max(start);
+            let to = ((slot + 1) * len).min(end);
+            Self::add(buckets, slot, rate * (to - from) as f32);
+        }
+    }
+
+    fn sum<const N: usize>(buckets: &[Self; N], slot: i64) -> f32 {
+        buckets
+            .iter()
+            .filter(|x| x.slot <= slot && slot - x.slot < N as i64)
+            .map(|x| x.amount)
+            .sum()
+    }
+}
+
+/// Persistent state of a [RainAccumulator].
+///
+/// Store this state, e.g. with the `serde` feature, and restore it with
+/// [RainAccumulator::with_state] to keep totals across restarts.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RainAccumulatorState {
+    /// Console local time and daily rain counter of the last reading.
+    last: Option<(i64, f32)>,
+    /// UTC timestamp of the last reading with outdoor data.
+    last_time: Option<i64>,
+    /// UTC timestamp of the last reading.
+    now: Option<i64>,
+    /// Five minute buckets of the last hour.
+    recent: [RainBucket; 12],
+    /// Hourly buckets of the last day.
+    hourly: [RainBucket; 24],
+    day: i32,
+    day_total: f32,
+    month: i32,
+    month_total: f32,
+    year: i32,
+    year_total: f32,
+    storm_start: i64,
+    last_rain: Option<i64>,
+    storm_total: f32,
+}
+
+/// A rain event without dry periods longer than
+/// [RainAccumulatorConfig::storm_gap].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StormEvent {
+    /// Time of the first rain of the event.
+    pub start: OffsetDateTime,
+    /// Time of the most recent rain of the event.
+    pub last_rain: OffsetDateTime,
+    /// Rain amount of the event.
+    pub total: Millimeters,
+}
+
+/// Rain totals at the time of the latest reading.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RainTotals {
+    /// Rain in the last hour with five minute resolution.
+    pub last_hour: Millimeters,
+    /// Rain in the last 24 hours with one hour resolution.
+    pub last_24h: Millimeters,
+    /// Rain since midnight in the reporting timezone.
+    pub today: Millimeters,
+    /// Rain since the start of the month.
+    pub month: Millimeters,
+    /// Rain since the start of the year.
+    pub year: Millimeters,
+    /// The ongoing storm event, if any.
+    pub storm: Option<StormEvent>,
+}
+
+/// Accumulates rain from the daily rain counter of successive readings.
+///
+/// Resets of the daily counter at the consoles midnight and console
+/// restarts are detected by a decreasing counter or a changed console
+/// day. In that case, the counter value is counted as new rain and rain
+/// between the last reading and the reset is lost. Rain after a gap in
+/// the readings is spread evenly across the gap in the last hour and
+/// last 24 hour totals. All other totals and storm events attribute it to
+/// the time of the next reading.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RainAccumulator {
+    config: RainAccumulatorConfig,
+    state: RainAccumulatorState,
+}
+
+impl RainAccumulator {
+    const RECENT_SLOT: i64 = 300;
+    const HOURLY_SLOT: i64 = 3600;
+
+    /// Creates an empty accumulator.
+    pub fn new(config: RainAccumulatorConfig) -> Self {
+        Self::with_state(config, RainAccumulatorState::default())
+    }
+
+    /// Creates an accumulator from previously stored state.
+    pub fn with_state(
+        config: RainAccumulatorConfig,
+        state: RainAccumulatorState,
+    ) -> Self {
+        Self { config, state }
+    }
+
+    /// Returns the current state for persistence.
+    pub fn state(&self) -> &RainAccumulatorState {
+        &self.state
+    }
+
+    /// Adds a reading. Readings older than the latest one are ignored.
+    /// Fails if the console time cannot be converted to UTC.
+    pub fn push(&mut self, data: &Ws6in1Data) -> Result<()> {
+        let time = self
+            .config
+            .console
+            .utc_datetime(data, self.config.dst_policy)?;
+        let now = time.unix_timestamp();
+        if self.state.now.is_some_and(|x| now < x) {
+            return Ok(());
+        }
+
+        self.rollover(time);
+        self.state.now = Some(now);
+
+        if let Some(outdoor) = &data.outdoor {
+            let sample = (data.local_timestamp, outdoor.rain_day);
+            if let Some((timestamp, rain)) = self.state.last {
+                let amount =
+                    rain_increment((timestamp, Millimeters(rain)), sample);
+                if amount.0 > 0.0 {
+                    let start = self.state.last_time.unwrap_or(now);
+                    self.add(start, now, amount.0);
+                }
+            }
+            self.state.last = Some((sample.0, sample.1 .0));
+            self.state.last_time = Some(now);
+        }
+
+        Ok(())
+    }
+
+    fn rollover(&mut self, time: OffsetDateTime) {
+        let local = self.config.reporting.local_datetime(time);
+        let state = &mut self.state;
+        let day = local.date().to_julian_day();
+        let month = local.year() * 12 + i32::from(u8::from(local.month()));
+
+        if state.day != day {
+            state.day = day;
+            state.day_total = 0.0;
+        }
+        if state.month != month {
+            state.month = month;
+            state.month_total = 0.0;
+        }
+        if state.year != local.year() {
+            state.year = local.year();
+            state.year_total = 0.0;
+        }
+    }
+
+    fn add(&mut self, start: i64, now: i64, amount: f32) {
+        let storm_active = self.storm_active(now);
+        let state = &mut self.state;
+        RainBucket::spread(
+            &mut state.recent,
+            Self::RECENT_SLOT,
+            start,
+            now,
+            amount,
+        );
+        RainBucket::spread(
+            &mut state.hourly,
+            Self::HOURLY_SLOT,
+            start,
+            now,
+            amount,
+        );
+        state.day_total += amount;
+        state.month_total += amount;
+        state.year_total += amount;
+
+        if !storm_active {
+            state.storm_start = now;
+            state.storm_total = 0.0;
+        }
+        state.storm_total += amount;
+        state.last_rain = Some(now);
+    }
+
+    fn storm_active(&self, now: i64) -> bool {
+        self.state
+            .last_rain
+            .is_some_and(|x| now - x <= self.config.storm_gap.whole_seconds())
+    }
+
+    /// Returns the totals at the time of the latest reading.
+    pub fn totals(&self) -> RainTotals {
+        let state = &self.state;
+        let Some(now) = state.now else {
+            return RainTotals::default();
+        };
+
+        let storm = match (self.storm_active(now), state.last_rain) {
+            (true, Some(last_rain)) => {
+                let start =
+                    OffsetDateTime::from_unix_timestamp(state.storm_start);
+                let last_rain = OffsetDateTime::from_unix_timestamp(last_rain);
+                match (start, last_rain) {
+                    (Ok(start), Ok(last_rain)) => Some(StormEvent {
+                        start,
+                        last_rain,
+                        total: Millimeters(state.storm_total),
+                    }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        RainTotals {
+            last_hour: Millimeters(RainBucket::sum(
+                &state.recent,
+                now.div_euclid(Self::RECENT_SLOT),
+            )),
+            last_24h: Millimeters(RainBucket::sum(
+                &state.hourly,
+                now.div_euclid(Self::HOURLY_SLOT),
+            )),
+            today: Millimeters(state.day_total),
+            month: Millimeters(state.month_total),
+            year: Millimeters(state.year_total),
+            storm,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{macros::datetime, macros::offset, PrimitiveDateTime};
+
+    use super::*;
+    use crate::parser::Ws6in1OutdoorData;
+
+    fn reading(datetime: PrimitiveDateTime, rain: f32) -> Ws6in1Data {
+        Ws6in1Data::builder()
+            .local_datetime(datetime)
+            .outdoor(Ws6in1OutdoorData {
+                rain_day: Millimeters(rain),
+                ..Default::default()
+            })
+            .build()
+            .unwrap()
+    }
+
+    fn assert_close(expected: f32, actual: Millimeters) {
+        assert!(
+            (expected - actual.0).abs() < 1e-4,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_accumulate_across_reset() {
+        let mut rain = RainAccumulator::new(RainAccumulatorConfig::default());
+        rain.push(&reading(datetime!(2020-01-31 22:00), 3.0))
+            .unwrap();
+        rain.push(&reading(datetime!(2020-01-31 23:00), 4.0))
+            .unwrap();
+        rain.push(&reading(datetime!(2020-01-31 23:50), 5.0))
+            .unwrap();
+
+        let totals = rain.totals();
+        assert_close(2.0, totals.today);
+        // Rain is spread across the gaps between the readings.
+        assert_close(1.0 + 5.0 / 60.0, totals.last_hour);
+
+        // Missed reading at midnight, the counter was reset meanwhile.
+        rain.push(&reading(datetime!(2020-02-01 00:20), 1.0))
+            .unwrap();
+        // Console restart resets the counter.
+        rain.push(&reading(datetime!(2020-02-01 00:40), 0.5))
+            .unwrap();
+        rain.push(&reading(datetime!(2020-02-01 00:50), 0.7))
+            .unwrap();
+
+        let totals = rain.totals();
+        assert_close(1.7, totals.today);
+        assert_close(1.7, totals.month);
+        assert_close(3.7, totals.year);
+        assert_close(25.0 / 30.0 + 0.7, totals.last_hour);
+        assert_close(3.7, totals.last_24h);
+
+        let storm = totals.storm.unwrap();
+        assert_eq!(datetime!(2020-01-31 23:00 UTC), storm.start);
+        assert_close(3.7, storm.total);
+    }
+
+    #[test]
+    fn test_reporting_timezone() {
+        let config = RainAccumulatorConfig {
+            console: StationTimezone::Fixed(offset!(+1)),
+            ..Default::default()
+        };
+        let mut rain = RainAccumulator::new(config);
+        rain.push(&reading(datetime!(2020-01-17 00:10), 0.0))
+            .unwrap();
+        rain.push(&reading(datetime!(2020-01-17 00:50), 2.0))
+            .unwrap();
+
+        // Rain fell before midnight UTC.
+        rain.push(&reading(datetime!(2020-01-17 01:10), 2.0))
+            .unwrap();
+        assert_close(0.0, rain.totals().today);
+        assert_close(2.0, rain.totals().last_24h);
+    }
+
+    #[test]
+    fn test_restore_state_and_storm_end() {
+        let config = RainAccumulatorConfig {
+            storm_gap: Duration::hours(6),
+            ..Default::default()
+        };
+        let mut rain = RainAccumulator::new(config);
+        rain.push(&reading(datetime!(2020-01-17 08:00), 0.0))
+            .unwrap();
+        rain.push(&reading(datetime!(2020-01-17 09:00), 1.5))
+            .unwrap();
+
+        let mut restored = RainAccumulator::with_state(config, *rain.state());
+        restored
+            .push(&reading(datetime!(2020-01-17 12:00), 2.0))
+            .unwrap();
+        assert_close(2.0, restored.totals().today);
+        assert_close(2.0, restored.totals().storm.unwrap().total);
+
+        restored
+            .push(&reading(datetime!(2020-01-17 19:00), 2.0))
+            .unwrap();
+        assert_eq!(None, restored.totals().storm);
+        assert_close(0.0, restored.totals().last_hour);
+
+        restored
+            .push(&reading(datetime!(2020-01-17 20:00), 2.4))
+            .unwrap();
+        assert_close(0.4, restored.totals().storm.unwrap().total);
+        assert_close(2.4, restored.totals().today);
+    }
+}
@@ -0,0 +1,269 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use time::Duration;
+
+use super::timestamp_ms;
+use crate::{
+    parser::{rain_increment, Ws6in1Data},
+    units::{Millimeters, MillimetersPerHour},
+};
+
+/// Rain intensity classes according to WMO-No. 8.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum RainIntensity {
+    /// No rain.
+    None,
+    /// Less than 2.5 mm/h.
+    Light,
+    /// 2.5 to 10 mm/h.
+    Moderate,
+    /// 10 to 50 mm/h.
+    Heavy,
+    /// More than 50 mm/h.
+    Violent,
+}
+
+impl RainIntensity {
+    /// Classifies a rain rate.
+    pub fn from_rate(rate: MillimetersPerHour) -> Self {
+        match rate.0 {
+            x if x <= 0.0 => Self::None,
+            x if x < 2.5 => Self::Light,
+            x if x < 10.0 => Self::Moderate,
+            x if x <= 50.0 => Self::Heavy,
+            _ => Self::Violent,
+        }
+    }
+}
+
+/// Estimated rain rate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RainRate {
+    /// Average rain rate within the window.
+    pub rate: MillimetersPerHour,
+    /// Rate corresponding to a single bucket tip within the window.
+    /// Rates are quantized to multiples of this value.
+    pub resolution: MillimetersPerHour,
+    /// Intensity class of the rate.
+    pub intensity: RainIntensity,
+}
+
+/// Configuration of a [RainRateEstimator].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RainRateConfig {
+    /// Length of the sliding window. It is limited to one minute up to
+    /// [RainRateEstimator::MAX_WINDOW].
+    pub window: Duration,
+    /// Amount of rain per tip of the rain gauge bucket. Increments are not
+    /// rounded if this is zero or negative.
+    pub bucket: Millimeters,
+}
+
+impl Default for RainRateConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::minutes(15),
+            bucket: Millimeters(0.3),
+        }
+    }
+}
+
+/// Estimates the rain rate from changes of the daily rain counter of
+/// successive readings within a sliding window.
+///
+/// Readings are timed by their host receive time if available and by the
+/// console time otherwise. Increments are rounded to whole bucket tips
+/// and the remainder is carried over to the next increment. Only the
+/// latest [RainRateEstimator::CAPACITY] increments are kept, older ones
+/// are dropped even if they are still within the window.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RainRateEstimator {
+    config: RainRateConfig,
+    /// Millisecond timestamp and rain amount of increments.
+    increments: [Option<(i64, f32)>; Self::CAPACITY],
+    next: usize,
+    last: Option<(i64, f32)>,
+    now: Option<i64>,
+    /// Rain not yet counted as a whole bucket tip.
+    remainder: f32,
+}
+
+impl RainRateEstimator {
+    /// Maximum amount of rain increments within the window. This covers
+    /// windows up to 30 minutes at the consoles one minute update rate.
+    pub const CAPACITY: usize = 32;
+    /// Maximum length of the sliding window.
+    pub const MAX_WINDOW: Duration = Duration::minutes(30);
+
+    /// Creates an estimator with the given configuration.
+    pub fn new(mut config: RainRateConfig) -> Self {
+        config.window =
+            config.window.clamp(Duration::minutes(1), Self::MAX_WINDOW);
+        Self {
+            config,
+            increments: [None; Self::CAPACITY],
+            next: 0,
+            last: None,
+            now: None,
+            remainder: 0.0,
+        }
+    }
+
+    /// Adds a reading. Readings older than the latest one are ignored.
+    pub fn push(&mut self, data: &Ws6in1Data) {
+        let now = timestamp_ms(data);
+        if self.now.is_some_and(|x| now < x) {
+            return;
+        }
+        self.now = Some(now);
+
+        let Some(outdoor) = &data.outdoor else {
+            return;
+        };
+        let sample = (data.local_timestamp, outdoor.rain_day);
+        if let Some((timestamp, rain)) = self.last {
+            let amount =
+                rain_increment((timestamp, Millimeters(rain)), sample).0;
+            let bucket = self.config.bucket.0;
+            let amount = if bucket > 0.0 {
+                let total = amount + self.remainder;
+                let tips = libm::roundf(total / bucket).max(0.0);
+                self.remainder = total - tips * bucket;
+                tips * bucket
+            } else {
+                amount
+            };
+
+            if amount > 0.0 {
+                self.increments[self.next] = Some((now, amount));
+                self.next = (self.next + 1) % Self::CAPACITY;
+            }
+        }
+        self.last = Some((sample.0, sample.1 .0));
+    }
+
+    /// Returns the rain rate at the time of the latest reading.
+    pub fn rate(&self) -> RainRate {
+        let window = self.config.window.whole_milliseconds() as i64;
+        let hours = window as f32 / 3_600_000.0;
+        let amount: f32 = match self.now {
+            Some(now) => self
+                .increments
+                .iter()
+                .flatten()
+                .filter(|(timestamp, _)| now - timestamp < window)
+                .map(|(_, amount)| amount)
+                .sum(),
+            None => 0.0,
+        };
+
+        let rate = MillimetersPerHour(amount / hours);
+        RainRate {
+            rate,
+            resolution: MillimetersPerHour(
+                self.config.bucket.0.max(0.0) / hours,
+            ),
+            intensity: RainIntensity::from_rate(rate),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Ws6in1OutdoorData;
+
+    fn reading(minute: i64, rain: f32) -> Ws6in1Data {
+        Ws6in1Data::builder()
+            .local_timestamp(1579282200 + minute * 60)
+            .outdoor(Ws6in1OutdoorData {
+                rain_day: Millimeters(rain),
+                ..Default::default()
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rain_rate() {
+        let mut estimator = RainRateEstimator::new(RainRateConfig::default());
+        assert_eq!(RainIntensity::None, estimator.rate().intensity);
+
+        for (minute, rain) in [(0, 1.2), (5, 1.5), (10, 2.1), (15, 2.7)] {
+            estimator.push(&reading(minute, rain));
+        }
+
+        let rate = estimator.rate();
+        assert!((rate.rate.0 - 6.0).abs() < 1e-4);
+        assert!((rate.resolution.0 - 1.2).abs() < 1e-4);
+        assert_eq!(RainIntensity::Moderate, rate.intensity);
+
+        estimator.push(&reading(29, 2.7));
+        assert_eq!(RainIntensity::Light, estimator.rate().intensity);
+        estimator.push(&reading(30, 2.7));
+        assert_eq!(RainIntensity::None, estimator.rate().intensity);
+    }
+
+    #[test]
+    fn test_rain_rate_across_reset() {
+        let mut estimator = RainRateEstimator::new(RainRateConfig {
+            window: Duration::minutes(10),
+            bucket: Millimeters(0.3),
+        });
+        estimator.push(&reading(0, 12.0));
+        estimator.push(&reading(5, 0.6));
+
+        assert!((estimator.rate().rate.0 - 3.6).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_carry_bucket_remainder() {
+        let mut estimator = RainRateEstimator::new(RainRateConfig::default());
+        for (minute, rain) in [(0, 0.0), (1, 0.1), (2, 0.2), (3, 0.3)] {
+            estimator.push(&reading(minute, rain));
+        }
+        assert!((estimator.rate().rate.0 - 1.2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_unquantized_and_limited_window() {
+        let mut estimator = RainRateEstimator::new(RainRateConfig {
+            window: Duration::ZERO,
+            bucket: Millimeters(0.0),
+        });
+        estimator.push(&reading(0, 0.0));
+        estimator.push(&reading(1, 0.1));
+        assert!((estimator.rate().rate.0 - 6.0).abs() < 1e-4);
+
+        let estimator = RainRateEstimator::new(RainRateConfig {
+            window: Duration::hours(2),
+            ..Default::default()
+        });
+        assert!((estimator.rate().resolution.0 - 0.6).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_intensity_classes() {
+        let class = |x| RainIntensity::from_rate(MillimetersPerHour(x));
+        assert_eq!(RainIntensity::Light, class(0.3));
+        assert_eq!(RainIntensity::Moderate, class(2.5));
+        assert_eq!(RainIntensity::Heavy, class(12.0));
+        assert_eq!(RainIntensity::Violent, class(60.0));
+    }
+}
@@ -0,0 +1,285 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use crate::{parser::Ws6in1Data, units::Hectopascal};
+
+/// Classification of the pressure change over three hours.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PressureTrend {
+    /// Falls by more than 6 hPa.
+    FallingVeryRapidly,
+    /// Falls by 3.6 to 6 hPa.
+    FallingQuickly,
+    /// Falls by 1.6 to 3.5 hPa.
+    Falling,
+    /// Falls by 0.1 to 1.5 hPa.
+    FallingSlowly,
+    /// Changes by less than 0.1 hPa.
+    Steady,
+    /// Rises by 0.1 to 1.5 hPa.
+    RisingSlowly,
+    /// Rises by 1.6 to 3.5 hPa.
+    Rising,
+    /// Rises by 3.6 to 6 hPa.
+    RisingQuickly,
+    /// Rises by more than 6 hPa.
+    RisingVeryRapidly,
+}
+
+impl PressureTrend {
+    /// Classifies a three hour pressure change.
+    pub fn from_change(change: Hectopascal) -> Self {
+        let magnitude = libm::fabsf(change.0);
+        let rising = change.0 > 0.0;
+        match magnitude {
+            x if x < PressureTendency::STEADY_THRESHOLD => Self::Steady,
+            x if x <= 1.55 && rising => Self::RisingSlowly,
+            x if x <= 1.55 => Self::FallingSlowly,
+            x if x <= 3.55 && rising => Self::Rising,
+            x if x <= 3.55 => Self::Falling,
+            x if x <= 6.0 && rising => Self::RisingQuickly,
+            x if x <= 6.0 => Self::FallingQuickly,
+            _ if rising => Self::RisingVeryRapidly,
+            _ => Self::FallingVeryRapidly,
+        }
+    }
+}
+
+/// Pressure tendency derived from the last three hours.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PressureTendency {
+    /// Pressure change over the last hour if known.
+    pub change_1h: Option<Hectopascal>,
+    /// Pressure change over the last three hours.
+    pub change_3h: Hectopascal,
+    /// Classification of the three hour change.
+    pub trend: PressureTrend,
+    /// WMO pressure tendency characteristic (code table 0200), 0 to 8.
+    pub characteristic: u8,
+}
+
+impl PressureTendency {
+    /// Changes below this amount in hPa are considered steady.
+    pub const STEADY_THRESHOLD: f32 = 0.1;
+
+    /// Derives the WMO characteristic from the pressure three hours ago,
+    /// ninety minutes ago and now.
+    pub fn characteristic(
+        start: Hectopascal,
+        middle: Hectopascal,
+        end: Hectopascal,
+    ) -> u8 {
+        let first = middle.0 - start.0;
+        let second = end.0 - middle.0;
+        let total = end.0 - start.0;
+        let sign = |x: f32| match x {
+            x if x > Self::STEADY_THRESHOLD => 1,
+            x if x < -Self::STEADY_THRESHOLD => -1,
+            _ => 0,
+        };
+
+        match (sign(total), sign(first), sign(second)) {
+            (0, 1, -1) => 0,
+            (0, -1, 1) => 5,
+            (0, _, _) => 4,
+            (1, 1, -1) => 0,
+            (1, 1, 0) => 1,
+            (1, 1, 1) if 2.0 * second < first => 1,
+            (1, 1, 1) if second > 2.0 * first => 3,
+            (1, _, 1) if first <= Self::STEADY_THRESHOLD => 3,
+            (1, _, _) => 2,
+            (_, -1, 1) => 5,
+            (_, -1, 0) => 6,
+            (_, -1, -1) if 2.0 * second > first => 6,
+            (_, -1, -1) if second < 2.0 * first => 8,
+            (_, _, -1) if first >= -Self::STEADY_THRESHOLD => 8,
+            _ => 7,
+        }
+    }
+}
+
+/// Tracks sea level pressure of successive readings and derives the
+/// pressure tendency.
+///
+/// The first reading of every ten minute interval of the last three hours
+/// is stored based on the console time. Past pressures are therefore
+/// accurate to ten minutes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PressureTendencyTracker {
+    samples: [Option<(i64, f32)>; Self::SLOTS],
+    latest: Option<(i64, f32)>,
+}
+
+impl PressureTendencyTracker {
+    /// Time resolution of stored samples in seconds.
+    pub const RESOLUTION: i64 = 600;
+    const SLOTS: usize = (3 * 3600 / Self::RESOLUTION) as usize + 1;
+
+    /// Creates an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a reading. Readings older than the latest one discard the
+    /// stored history as the console clock was set back.
+    pub fn push(&mut self, data: &Ws6in1Data) {
+        let timestamp = data.local_timestamp;
+        if self.latest.is_some_and(|(x, _)| timestamp < x) {
+            *self = Self::default();
+        }
+
+        let pressure = data.indoor.baro_sea.0;
+        let slot = timestamp.div_euclid(Self::RESOLUTION);
+        let sample = &mut self.samples[Self::index(slot)];
+        if !sample.is_some_and(|(x, _)| x == slot) {
+            *sample = Some((slot, pressure));
+        }
+        self.latest = Some((timestamp, pressure));
+    }
+
+    fn index(slot: i64) -> usize {
+        slot.rem_euclid(Self::SLOTS as i64) as usize
+    }
+
+    /// Returns the stored pressure the given amount of seconds before the
+    /// latest reading, allowing one slot tolerance.
+    fn pressure_before(&self, seconds: i64) -> Option<Hectopascal> {
+        let (timestamp, pressure) = self.latest?;
+        if seconds == 0 {
+            return Some(Hectopascal(pressure));
+        }
+
+        let latest = timestamp.div_euclid(Self::RESOLUTION);
+        let target = (timestamp - seconds).div_euclid(Self::RESOLUTION);
+        [target, target + 1, target - 1]
+            .into_iter()
+            .filter(|x| *x <= latest && latest - *x < Self::SLOTS as i64)
+            .find_map(|slot| match self.samples[Self::index(slot)] {
+                Some((x, pressure)) if x == slot => Some(Hectopascal(pressure)),
+                _ => None,
+            })
+    }
+
+    /// Returns the pressure change over the last hour.
+    pub fn change_1h(&self) -> Option<Hectopascal> {
+        let now = self.pressure_before(0)?;
+        let before = self.pressure_before(3600)?;
+        Some(Hectopascal(now.0 - before.0))
+    }
+
+    /// Returns the pressure change over the last three hours.
+    pub fn change_3h(&self) -> Option<Hectopascal> {
+        let now = self.pressure_before(0)?;
+        let before = self.pressure_before(3 * 3600)?;
+        Some(Hectopascal(now.0 - before.0))
+    }
+
+    /// Returns the pressure tendency or [None] if less than three hours
+    /// of data are available.
+    pub fn tendency(&self) -> Option<PressureTendency> {
+        let end = self.pressure_before(0)?;
+        let middle = self.pressure_before(3 * 1800)?;
+        let start = self.pressure_before(3 * 3600)?;
+        let change_3h = Hectopascal(end.0 - start.0);
+
+        Some(PressureTendency {
+            change_1h: self.change_1h(),
+            change_3h,
+            trend: PressureTrend::from_change(change_3h),
+            characteristic: PressureTendency::characteristic(
+                start, middle, end,
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(
+        tracker: &mut PressureTendencyTracker,
+        pressure: impl Fn(f32) -> f32,
+    ) {
+        for minute in (0..=180).step_by(5) {
+            let data = Ws6in1Data::builder()
+                .local_timestamp(1579282200 + minute * 60)
+                .pressure(
+                    Hectopascal(pressure(minute as f32)),
+                    Hectopascal(950.0),
+                )
+                .build()
+                .unwrap();
+            tracker.push(&data);
+        }
+    }
+
+    #[test]
+    fn test_falling_pressure() {
+        let mut tracker = PressureTendencyTracker::new();
+        feed(&mut tracker, |minute| 1013.0 - minute / 45.0);
+
+        let tendency = tracker.tendency().unwrap();
+        assert!((tendency.change_3h.0 + 4.0).abs() < 0.01);
+        assert!((tendency.change_1h.unwrap().0 + 1.333).abs() < 0.01);
+        assert_eq!(PressureTrend::FallingQuickly, tendency.trend);
+        assert_eq!(7, tendency.characteristic);
+    }
+
+    #[test]
+    fn test_rising_then_falling_pressure() {
+        let mut tracker = PressureTendencyTracker::new();
+        feed(&mut tracker, |minute| {
+            1013.0 + 1.0 - (minute - 90.0).abs() / 90.0
+        });
+
+        let tendency = tracker.tendency().unwrap();
+        assert_eq!(PressureTrend::Steady, tendency.trend);
+        assert_eq!(0, tendency.characteristic);
+    }
+
+    #[test]
+    fn test_insufficient_history() {
+        let mut tracker = PressureTendencyTracker::new();
+        assert_eq!(None, tracker.tendency());
+
+        feed(&mut tracker, |_| 1013.0);
+        assert_eq!(4, tracker.tendency().unwrap().characteristic);
+
+        let data = Ws6in1Data::builder().local_timestamp(0).build().unwrap();
+        tracker.push(&data);
+        assert_eq!(None, tracker.tendency());
+    }
+
+    #[test]
+    fn test_characteristic_codes() {
+        let code = |a, b, c| {
+            PressureTendency::characteristic(
+                Hectopascal(a),
+                Hectopascal(b),
+                Hectopascal(c),
+            )
+        };
+        assert_eq!(1, code(1000.0, 1002.0, 1002.0));
+        assert_eq!(2, code(1000.0, 1001.0, 1002.0));
+        assert_eq!(3, code(1000.0, 1000.0, 1002.0));
+        assert_eq!(5, code(1002.0, 1000.0, 1001.0));
+        assert_eq!(6, code(1002.0, 1000.0, 1000.0));
+        assert_eq!(8, code(1002.0, 1002.0, 1000.0));
+    }
+}
@@ -0,0 +1,258 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use time::Duration;
+
+use crate::{parser::Ws6in1Data, units::UvIndex};
+
+/// WHO UV index exposure categories.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum UvCategory {
+    /// UV index 0 to 2.
+    Low,
+    /// UV index 3 to 5.
+    Moderate,
+    /// UV index 6 to 7.
+    High,
+    /// UV index 8 to 10.
+    VeryHigh,
+    /// UV index 11 and above.
+    Extreme,
+}
+
+impl UvCategory {
+    /// Classifies a UV index after rounding it to a whole number as
+    /// recommended by WHO.
+    pub fn from_index(uv_index: UvIndex) -> Self {
+        match libm::roundf(uv_index.0) {
+            x if x < 3.0 => Self::Low,
+            x if x < 6.0 => Self::Moderate,
+            x if x < 8.0 => Self::High,
+            x if x < 11.0 => Self::VeryHigh,
+            _ => Self::Extreme,
+        }
+    }
+
+    /// Returns the English name of the category.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Low => "Low",
+            Self::Moderate => "Moderate",
+            Self::High => "High",
+            Self::VeryHigh => "Very high",
+            Self::Extreme => "Extreme",
+        }
+    }
+
+    /// Returns the WHO colour code as RGB hex string.
+    pub fn color(self) -> &'static str {
+        match self {
+            Self::Low => "#289500",
+            Self::Moderate => "#F7E400",
+            Self::High => "#F85900",
+            Self::VeryHigh => "#D8001D",
+            Self::Extreme => "#6B49C8",
+        }
+    }
+
+    /// Returns the WHO sun protection recommendation.
+    pub fn protection(self) -> &'static str {
+        match self {
+            Self::Low => "No protection required. You can safely stay outside.",
+            Self::Moderate | Self::High => {
+                "Protection required. Seek shade during midday hours. \
+                Slip on a shirt, slop on sunscreen and slap on a hat."
+            }
+            Self::VeryHigh | Self::Extreme => {
+                "Extra protection required. Avoid being outside during \
+                midday hours. Make sure you seek shade. Shirt, sunscreen \
+                and hat are a must."
+            }
+        }
+    }
+}
+
+/// Fitzpatrick skin phototypes.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum SkinType {
+    /// Always burns, never tans.
+    I,
+    /// Usually burns, tans minimally.
+    II,
+    /// Sometimes burns, tans uniformly.
+    III,
+    /// Burns minimally, always tans well.
+    IV,
+    /// Very rarely burns, tans very easily.
+    V,
+    /// Never burns.
+    VI,
+}
+
+impl SkinType {
+    /// Returns the typical minimal erythema dose in J/m² of erythemally
+    /// weighted UV radiation.
+    pub fn minimal_erythema_dose(self) -> f32 {
+        match self {
+            Self::I => 200.0,
+            Self::II => 250.0,
+            Self::III => 350.0,
+            Self::IV => 450.0,
+            Self::V => 600.0,
+            Self::VI => 1000.0,
+        }
+    }
+}
+
+impl UvIndex {
+    /// Erythemally weighted irradiance in W/m² per UV index unit.
+    pub const IRRADIANCE: f32 = 0.025;
+
+    /// Returns the WHO exposure category.
+    pub fn category(self) -> UvCategory {
+        UvCategory::from_index(self)
+    }
+
+    /// Estimates the unprotected exposure time until sunburn for the
+    /// given skin type. Returns [None] if there is no UV radiation.
+    pub fn time_to_burn(self, skin: SkinType) -> Option<Duration> {
+        if self.0.is_nan() || self.0 <= 0.0 {
+            return None;
+        }
+
+        let seconds =
+            skin.minimal_erythema_dose() / (self.0 * Self::IRRADIANCE);
+        Some(Duration::seconds(libm::roundf(seconds) as i64))
+    }
+}
+
+/// Maximum UV index of a day.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UvMaximum {
+    /// Highest UV index of the day.
+    pub uv_index: UvIndex,
+    /// Console time of the reading with the highest UV index.
+    pub local_timestamp: i64,
+}
+
+/// Tracks the maximum UV index of the current console day.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct UvDailyMax {
+    day: Option<i64>,
+    max: Option<UvMaximum>,
+}
+
+impl UvDailyMax {
+    /// Creates an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a reading. A new console day restarts tracking. Readings from
+    /// an earlier day are ignored.
+    pub fn push(&mut self, data: &Ws6in1Data) {
+        let day = data.local_timestamp.div_euclid(86400);
+        if self.day.is_some_and(|x| day < x) {
+            return;
+        }
+        if self.day != Some(day) {
+            self.day = Some(day);
+            self.max = None;
+        }
+
+        let Some(outdoor) = &data.outdoor else {
+            return;
+        };
+        if self.max.is_none_or(|x| outdoor.uv_index > x.uv_index) {
+            self.max = Some(UvMaximum {
+                uv_index: outdoor.uv_index,
+                local_timestamp: data.local_timestamp,
+            });
+        }
+    }
+
+    /// Returns the maximum of the current day.
+    pub fn max(&self) -> Option<&UvMaximum> {
+        self.max.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{macros::datetime, PrimitiveDateTime};
+
+    use super::*;
+    use crate::parser::Ws6in1OutdoorData;
+
+    #[test]
+    fn test_uv_categories() {
+        assert_eq!(UvCategory::Low, UvIndex(2.4).category());
+        assert_eq!(UvCategory::Moderate, UvIndex(2.5).category());
+        assert_eq!(UvCategory::High, UvIndex(7.0).category());
+        assert_eq!(UvCategory::VeryHigh, UvIndex(10.4).category());
+        assert_eq!(UvCategory::Extreme, UvIndex(11.0).category());
+        assert_eq!("#D8001D", UvCategory::VeryHigh.color());
+    }
+
+    #[test]
+    fn test_time_to_burn() {
+        assert_eq!(
+            Some(Duration::seconds(1250)),
+            UvIndex(8.0).time_to_burn(SkinType::II)
+        );
+        assert_eq!(
+            Some(Duration::seconds(5000)),
+            UvIndex(8.0).time_to_burn(SkinType::VI)
+        );
+        assert_eq!(None, UvIndex(0.0).time_to_burn(SkinType::I));
+        assert_eq!(None, UvIndex(f32::NAN).time_to_burn(SkinType::I));
+    }
+
+    #[test]
+    fn test_daily_max() {
+        let reading = |datetime: PrimitiveDateTime, uv_index| {
+            Ws6in1Data::builder()
+                .local_datetime(datetime)
+                .outdoor(Ws6in1OutdoorData {
+                    uv_index: UvIndex(uv_index),
+                    ..Default::default()
+                })
+                .build()
+                .unwrap()
+        };
+
+        let mut tracker = UvDailyMax::new();
+        tracker.push(&reading(datetime!(2020-06-17 11:00), 5.0));
+        tracker.push(&reading(datetime!(2020-06-17 13:00), 7.5));
+        tracker.push(&reading(datetime!(2020-06-17 15:00), 6.0));
+
+        let max = tracker.max().unwrap();
+        assert_eq!(UvIndex(7.5), max.uv_index);
+        assert_eq!(
+            datetime!(2020-06-17 13:00).assume_utc().unix_timestamp(),
+            max.local_timestamp
+        );
+
+        tracker.push(&reading(datetime!(2020-06-18 06:00), 0.5));
+        assert_eq!(UvIndex(0.5), tracker.max().unwrap().uv_index);
+
+        // Late reading from the previous day.
+        tracker.push(&reading(datetime!(2020-06-17 16:00), 4.0));
+        assert_eq!(UvIndex(0.5), tracker.max().unwrap().uv_index);
+    }
+}
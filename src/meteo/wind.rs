@@ -0,0 +1,451 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use time::{Duration, OffsetDateTime};
+
+use super::timestamp_ms;
+use crate::{
+    parser::{Ws6in1Data, Ws6in1OutdoorData},
+    units::{Degrees, MetersPerSecond},
+};
+
+/// Running sums for scalar and vector averaging of wind.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct WindSums {
+    count: u32,
+    speed: f32,
+    /// East component of the direction the wind blows from.
+    x: f32,
+    /// North component of the direction the wind blows from.
+    y: f32,
+}
+
+impl WindSums {
+    fn add(&mut self, speed: MetersPerSecond, direction: Degrees) {
+        let angle = f32::from(direction.0).to_radians();
+        self.count += 1;
+        self.speed += speed.0;
+        self.x += speed.0 * libm::sinf(angle);
+        self.y += speed.0 * libm::cosf(angle);
+    }
+
+    fn scalar_speed(&self) -> Option<MetersPerSecond> {
+        (self.count != 0)
+            .then(|| MetersPerSecond(self.speed / self.count as f32))
+    }
+
+    fn vector_speed(&self) -> Option<MetersPerSecond> {
+        let n = self.count as f32;
+        (self.count != 0).then(|| {
+            MetersPerSecond(libm::sqrtf(self.x * self.x + self.y * self.y) / n)
+        })
+    }
+
+    fn direction(&self) -> Option<Degrees> {
+        if self.x == 0.0 && self.y == 0.0 {
+            return None;
+        }
+
+        let degrees = libm::atan2f(self.x, self.y).to_degrees();
+        let degrees = libm::roundf(degrees + 360.0) as u16 % 360;
+        Some(Degrees(degrees))
+    }
+}
+
+/// Averaged wind over a period.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindAverage {
+    /// Arithmetic mean of the wind speed.
+    pub scalar_speed: MetersPerSecond,
+    /// Magnitude of the mean wind vector.
+    pub vector_speed: MetersPerSecond,
+    /// Direction of the mean wind vector. [None] if calm or the wind
+    /// vectors cancel out.
+    pub direction: Option<Degrees>,
+    /// Highest gust within the period.
+    pub gust: MetersPerSecond,
+    /// Amount of readings in the period. Windowed averages count merged
+    /// readings once.
+    pub count: u32,
+}
+
+impl WindAverage {
+    fn new(sums: &WindSums, gust: MetersPerSecond) -> Option<Self> {
+        Some(Self {
+            scalar_speed: sums.scalar_speed()?,
+            vector_speed: sums.vector_speed()?,
+            direction: sums.direction(),
+            gust,
+            count: sums.count,
+        })
+    }
+}
+
+/// Highest gust since the last reset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PeakGust {
+    /// Gust speed.
+    pub speed: MetersPerSecond,
+    /// Wind direction at the time of the gust.
+    pub direction: Degrees,
+    /// Console time of the reading.
+    pub local_timestamp: i64,
+    /// Host receive time of the reading if available.
+    pub received: Option<OffsetDateTime>,
+}
+
+/// Histogram of wind directions by speed class in 16 sectors.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct WindRose {
+    counts: [[u32; WindRose::CLASSES]; 16],
+    calm: u32,
+}
+
+impl WindRose {
+    /// Upper speed limits in m/s of the speed classes except the last.
+    /// Speeds below the calm limit are counted as calm.
+    pub const CLASS_LIMITS: [f32; 5] = [2.0, 4.0, 6.0, 8.0, 11.0];
+    /// Amount of speed classes.
+    pub const CLASSES: usize = Self::CLASS_LIMITS.len() + 1;
+    /// Speeds below this value are considered calm.
+    pub const CALM: MetersPerSecond = MetersPerSecond(0.5);
+
+    /// Returns the speed class of a wind speed or [None] if calm.
+    pub fn class(speed: MetersPerSecond) -> Option<usize> {
+        if speed < Self::CALM {
+            return None;
+        }
+        Some(
+            Self::CLASS_LIMITS
+                .iter()
+                .position(|limit| speed.0 < *limit)
+                .unwrap_or(Self::CLASS_LIMITS.len()),
+        )
+    }
+
+    fn add(&mut self, speed: MetersPerSecond, direction: Degrees) {
+        match Self::class(speed) {
+            Some(class) => self.counts[direction.sector()][class] += 1,
+            None => self.calm += 1,
+        }
+    }
+
+    /// Returns the amount of readings in the given sector, counted
+    /// clockwise from north, and speed class.
+    pub fn count(&self, sector: usize, class: usize) -> u32 {
+        self.counts
+            .get(sector)
+            .and_then(|x| x.get(class))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the amount of readings in the given sector.
+    pub fn sector_count(&self, sector: usize) -> u32 {
+        self.counts.get(sector).map_or(0, |x| x.iter().sum())
+    }
+
+    /// Returns the amount of calm readings.
+    pub fn calm(&self) -> u32 {
+        self.calm
+    }
+
+    /// Returns the total amount of readings.
+    pub fn total(&self) -> u32 {
+        (0..16).map(|x| self.sector_count(x)).sum::<u32>() + self.calm
+    }
+
+    /// Returns the relative frequency of a sector and speed class.
+    pub fn frequency(&self, sector: usize, class: usize) -> f32 {
+        match self.total() {
+            0 => 0.0,
+            total => self.count(sector, class) as f32 / total as f32,
+        }
+    }
+}
+
+/// A single wind reading.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct WindSample {
+    timestamp: i64,
+    speed: MetersPerSecond,
+    gust: MetersPerSecond,
+    direction: Degrees,
+}
+
+/// Aggregates wind readings into averages, peak gust and wind rose.
+///
+/// Averages over the last ten minutes are taken from stored readings
+/// timed by their host receive time if available and by the console time
+/// otherwise. Readings less than [WindAggregator::MIN_SPACING] after the
+/// previous stored one are merged into it, keeping the higher gust and
+/// the latest speed and direction. Peak gust and wind rose cover all
+/// readings since creation or the last [WindAggregator::reset].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindAggregator {
+    samples: [Option<WindSample>; Self::CAPACITY],
+    next: usize,
+    now: Option<i64>,
+    sums: WindSums,
+    peak: Option<PeakGust>,
+    rose: WindRose,
+}
+
+impl Default for WindAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WindAggregator {
+    /// Maximum supported averaging window.
+    pub const MAX_WINDOW: Duration = Duration::minutes(10);
+    /// Minimum time between readings stored for windowed averages.
+    pub const MIN_SPACING: Duration = Duration::seconds(10);
+    /// Maximum amount of readings considered for windowed averages.
+    pub const CAPACITY: usize = (Self::MAX_WINDOW.whole_seconds()
+        / Self::MIN_SPACING.whole_seconds())
+        as usize
+        + 1;
+
+    /// Creates an empty aggregator.
+    pub fn new() -> Self {
+        Self {
+            samples: [None; Self::CAPACITY],
+            next: 0,
+            now: None,
+            sums: WindSums::default(),
+            peak: None,
+            rose: WindRose::default(),
+        }
+    }
+
+    /// Adds a reading. Readings without outdoor data or older than the
+    /// latest one are ignored.
+    pub fn push(&mut self, data: &Ws6in1Data) {
+        let Some(outdoor) = &data.outdoor else {
+            return;
+        };
+        let timestamp = timestamp_ms(data);
+        if self.now.is_some_and(|x| timestamp < x) {
+            return;
+        }
+        self.now = Some(timestamp);
+
+        self.add(outdoor, timestamp);
+        if self.peak.is_none_or(|x| outdoor.wind_gust > x.speed) {
+            self.peak = Some(PeakGust {
+                speed: outdoor.wind_gust,
+                direction: outdoor.wind_dir,
+                local_timestamp: data.local_timestamp,
+                received: data.received,
+            });
+        }
+    }
+
+    fn add(&mut self, outdoor: &Ws6in1OutdoorData, timestamp: i64) {
+        let spacing = Self::MIN_SPACING.whole_milliseconds() as i64;
+        let last = (self.next + Self::CAPACITY - 1) % Self::CAPACITY;
+        match &mut self.samples[last] {
+            Some(x) if timestamp - x.timestamp < spacing => {
+                x.speed = outdoor.wind_actual;
+                x.direction = outdoor.wind_dir;
+                if outdoor.wind_gust > x.gust {
+                    x.gust = outdoor.wind_gust;
+                }
+            }
+            _ => {
+                self.samples[self.next] = Some(WindSample {
+                    timestamp,
+                    speed: outdoor.wind_actual,
+                    gust: outdoor.wind_gust,
+                    direction: outdoor.wind_dir,
+                });
+                self.next = (self.next + 1) % Self::CAPACITY;
+            }
+        }
+        self.sums.add(outdoor.wind_actual, outdoor.wind_dir);
+        self.rose.add(outdoor.wind_actual, outdoor.wind_dir);
+    }
+
+    /// Clears peak gust, wind rose and overall averages but keeps the
+    /// readings used for windowed averages.
+    pub fn reset(&mut self) {
+        self.sums = WindSums::default();
+        self.peak = None;
+        self.rose = WindRose::default();
+    }
+
+    /// Returns the average over the given window ending at the latest
+    /// reading. The window is limited to [Self::MAX_WINDOW].
+    pub fn average(&self, window: Duration) -> Option<WindAverage> {
+        let now = self.now?;
+        let window = window.min(Self::MAX_WINDOW).whole_milliseconds() as i64;
+        let mut sums = WindSums::default();
+        let mut gust = MetersPerSecond(0.0);
+
+        for sample in self.samples.iter().flatten() {
+            if now - sample.timestamp < window {
+                sums.add(sample.speed, sample.direction);
+                if sample.gust > gust {
+                    gust = sample.gust;
+                }
+            }
+        }
+
+        WindAverage::new(&sums, gust)
+    }
+
+    /// Returns the two minute average used for current wind reports.
+    pub fn average_2min(&self) -> Option<WindAverage> {
+        self.average(Duration::minutes(2))
+    }
+
+    /// Returns the ten minute average used for synoptic reports.
+    pub fn average_10min(&self) -> Option<WindAverage> {
+        self.average(Duration::minutes(10))
+    }
+
+    /// Returns the average over all readings since the last reset.
+    pub fn average_total(&self) -> Option<WindAverage> {
+        WindAverage::new(
+            &self.sums,
+            self.peak.map_or(MetersPerSecond(0.0), |x| x.speed),
+        )
+    }
+
+    /// Returns the highest gust since the last reset.
+    pub fn peak_gust(&self) -> Option<&PeakGust> {
+        self.peak.as_ref()
+    }
+
+    /// Returns the wind rose since the last reset.
+    pub fn rose(&self) -> &WindRose {
+        &self.rose
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn reading(minute: i64, speed: f32, gust: f32, dir: u16) -> Ws6in1Data {
+        Ws6in1Data::builder()
+            .local_timestamp(1579282200 + minute * 60)
+            .outdoor(Ws6in1OutdoorData {
+                wind_actual: MetersPerSecond(speed),
+                wind_gust: MetersPerSecond(gust),
+                wind_dir: Degrees(dir),
+                ..Default::default()
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_vector_average_around_north() {
+        let mut wind = WindAggregator::new();
+        wind.push(&reading(0, 4.0, 5.0, 350));
+        wind.push(&reading(1, 4.0, 7.0, 10));
+
+        let average = wind.average_2min().unwrap();
+        assert_eq!(Some(Degrees(0)), average.direction);
+        assert!((average.scalar_speed.0 - 4.0).abs() < 1e-4);
+        assert!((average.vector_speed.0 - 3.939).abs() < 1e-3);
+        assert_eq!(MetersPerSecond(7.0), average.gust);
+    }
+
+    #[test]
+    fn test_windowed_averages() {
+        let mut wind = WindAggregator::new();
+        for minute in 0..10 {
+            wind.push(&reading(minute, 2.0, 3.0, 90));
+        }
+        wind.push(&reading(10, 6.0, 12.0, 270));
+        wind.push(&reading(11, 0.0, 0.0, 270));
+
+        let short = wind.average_2min().unwrap();
+        assert_eq!(2, short.count);
+        assert_eq!(Some(Degrees(270)), short.direction);
+
+        let long = wind.average_10min().unwrap();
+        assert_eq!(10, long.count);
+        assert_eq!(Some(Degrees(90)), long.direction);
+
+        let peak = wind.peak_gust().unwrap();
+        assert_eq!(MetersPerSecond(12.0), peak.speed);
+        assert_eq!(Degrees(270), peak.direction);
+        assert_eq!(1579282200 + 600, peak.local_timestamp);
+
+        wind.reset();
+        assert_eq!(None, wind.peak_gust());
+        assert_eq!(0, wind.rose().total());
+        assert!(wind.average_2min().is_some());
+    }
+
+    #[test]
+    fn test_sub_minute_readings() {
+        let mut wind = WindAggregator::new();
+        let start = datetime!(2020-01-17 17:30 UTC);
+        let reading = |seconds, gust| {
+            Ws6in1Data::builder()
+                .local_timestamp(1579282200)
+                .received(start + Duration::seconds(seconds))
+                .outdoor(Ws6in1OutdoorData {
+                    wind_actual: MetersPerSecond(2.0),
+                    wind_gust: MetersPerSecond(gust),
+                    ..Default::default()
+                })
+                .build()
+                .unwrap()
+        };
+        for idx in 0..50 {
+            wind.push(&reading(idx * 12, if idx == 5 { 9.0 } else { 3.0 }));
+        }
+
+        let average = wind.average_10min().unwrap();
+        assert_eq!(50, average.count);
+        assert_eq!(MetersPerSecond(9.0), average.gust);
+
+        // Readings closer than the minimum spacing are merged.
+        wind.push(&reading(600, 3.0));
+        wind.push(&reading(605, 4.0));
+        let average = wind.average_10min().unwrap();
+        assert_eq!(50, average.count);
+        assert_eq!(MetersPerSecond(9.0), average.gust);
+        assert_eq!(MetersPerSecond(4.0), wind.average_2min().unwrap().gust);
+    }
+
+    #[test]
+    fn test_wind_rose() {
+        let mut wind = WindAggregator::new();
+        wind.push(&reading(0, 0.2, 1.0, 0));
+        wind.push(&reading(1, 3.0, 4.0, 0));
+        wind.push(&reading(2, 3.0, 4.0, 355));
+        wind.push(&reading(3, 12.0, 15.0, 225));
+
+        let rose = wind.rose();
+        assert_eq!(4, rose.total());
+        assert_eq!(1, rose.calm());
+        assert_eq!(2, rose.count(0, 1));
+        assert_eq!(1, rose.count(10, 5));
+        assert!((rose.frequency(0, 1) - 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,284 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use time::{Month, OffsetDateTime};
+
+use super::{PressureTendency, PressureTrend};
+use crate::{
+    parser::Ws6in1Data,
+    station::StationInfo,
+    units::{Degrees, Hectopascal},
+};
+
+/// Simplified pressure trend used by the Zambretti forecaster.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ZambrettiTrend {
+    /// Pressure falls.
+    Falling,
+    /// Pressure changes only slightly.
+    Steady,
+    /// Pressure rises.
+    Rising,
+}
+
+impl From<PressureTrend> for ZambrettiTrend {
+    /// Changes of up to 1.5 hPa in three hours are considered steady.
+    fn from(trend: PressureTrend) -> Self {
+        match trend {
+            PressureTrend::FallingVeryRapidly
+            | PressureTrend::FallingQuickly
+            | PressureTrend::Falling => Self::Falling,
+            PressureTrend::FallingSlowly
+            | PressureTrend::Steady
+            | PressureTrend::RisingSlowly => Self::Steady,
+            PressureTrend::Rising
+            | PressureTrend::RisingQuickly
+            | PressureTrend::RisingVeryRapidly => Self::Rising,
+        }
+    }
+}
+
+/// Hemisphere of the station location.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Hemisphere {
+    /// North of the equator.
+    #[default]
+    Northern,
+    /// South of the equator.
+    Southern,
+}
+
+/// A Zambretti forecast.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Zambretti {
+    /// Forecast code from 0 (settled fine) to 25 (stormy, much rain).
+    pub code: u8,
+    /// True if the pressure was outside of the 950 to 1050 hPa range
+    /// the forecaster is designed for.
+    pub out_of_range: bool,
+}
+
+impl Zambretti {
+    /// Lower pressure limit of the forecaster.
+    pub const PRESSURE_MIN: Hectopascal = Hectopascal(950.0);
+    /// Upper pressure limit of the forecaster.
+    pub const PRESSURE_MAX: Hectopascal = Hectopascal(1050.0);
+
+    const RANGE: f32 = Self::PRESSURE_MAX.0 - Self::PRESSURE_MIN.0;
+    const RISING: [u8; 22] = [
+        25, 25, 25, 24, 24, 19, 16, 12, 11, 9, 8, 6, 5, 2, 1, 1, 0, 0, 0, 0, 0,
+        0,
+    ];
+    const STEADY: [u8; 22] = [
+        25, 25, 25, 25, 25, 25, 23, 23, 22, 18, 15, 13, 10, 4, 1, 1, 0, 0, 0,
+        0, 0, 0,
+    ];
+    const FALLING: [u8; 22] = [
+        25, 25, 25, 25, 25, 25, 25, 25, 23, 23, 21, 20, 17, 14, 7, 3, 1, 1, 1,
+        0, 0, 0,
+    ];
+    /// Pressure adjustment in percent of the range for winds from the
+    /// 16 compass points, starting at north, on the northern hemisphere.
+    const WIND_ADJUSTMENT: [f32; 16] = [
+        6.0, 5.0, 5.0, 2.0, -0.5, -2.0, -5.0, -8.5, -12.0, -10.0, -6.0, -4.5,
+        -3.0, -0.5, 1.5, 3.0,
+    ];
+    const TEXT: [&'static str; 26] = [
+        "Settled fine",
+        "Fine weather",
+        "Becoming fine",
+        "Fine, becoming less settled",
+        "Fine, possible showers",
+        "Fairly fine, improving",
+        "Fairly fine, possible showers early",
+        "Fairly fine, showery later",
+        "Showery early, improving",
+        "Changeable, mending",
+        "Fairly fine, showers likely",
+        "Rather unsettled, clearing later",
+        "Unsettled, probably improving",
+        "Showery, bright intervals",
+        "Showery, becoming less settled",
+        "Changeable, some rain",
+        "Unsettled, short fine intervals",
+        "Unsettled, rain later",
+        "Unsettled, some rain",
+        "Mostly very unsettled",
+        "Occasional rain, worsening",
+        "Rain at times, very unsettled",
+        "Rain at frequent intervals",
+        "Rain, very unsettled",
+        "Stormy, may improve",
+        "Stormy, much rain",
+    ];
+
+    /// Calculates a forecast from sea level pressure, its three hour
+    /// trend, the wind direction if not calm, the month and hemisphere.
+    pub fn forecast(
+        pressure: Hectopascal,
+        trend: ZambrettiTrend,
+        wind_dir: Option<Degrees>,
+        month: Month,
+        hemisphere: Hemisphere,
+    ) -> Self {
+        let mut p = pressure.0;
+        let northern_summer = (4..=9).contains(&u8::from(month));
+        let summer = match hemisphere {
+            Hemisphere::Northern => northern_summer,
+            Hemisphere::Southern => !northern_summer,
+        };
+
+        if let Some(wind_dir) = wind_dir {
+            let mut sector = wind_dir.sector();
+            if hemisphere == Hemisphere::Southern {
+                sector = (sector + 8) % 16;
+            }
+            p += Self::RANGE * Self::WIND_ADJUSTMENT[sector] / 100.0;
+        }
+
+        if summer {
+            match trend {
+                ZambrettiTrend::Rising => p += Self::RANGE * 0.07,
+                ZambrettiTrend::Falling => p -= Self::RANGE * 0.07,
+                ZambrettiTrend::Steady => (),
+            }
+        }
+
+        let step = Self::RANGE / 22.0;
+        let option = libm::floorf((p - Self::PRESSURE_MIN.0) / step);
+        let idx = option.clamp(0.0, 21.0) as usize;
+        let options = match trend {
+            ZambrettiTrend::Rising => &Self::RISING,
+            ZambrettiTrend::Steady => &Self::STEADY,
+            ZambrettiTrend::Falling => &Self::FALLING,
+        };
+
+        Self {
+            code: options[idx],
+            out_of_range: pressure < Self::PRESSURE_MIN
+                || pressure > Self::PRESSURE_MAX,
+        }
+    }
+
+    /// Returns the traditional forecast letter from 'A' to 'Z'.
+    pub fn letter(&self) -> char {
+        char::from(b'A' + self.code)
+    }
+
+    /// Returns the English forecast text.
+    pub fn text(&self) -> &'static str {
+        Self::TEXT[self.code as usize]
+    }
+}
+
+impl Ws6in1Data {
+    /// Calculates a Zambretti forecast for this reading. Returns [None]
+    /// if the console time is invalid.
+    pub fn zambretti(
+        &self,
+        tendency: &PressureTendency,
+        station: &StationInfo,
+    ) -> Option<Zambretti> {
+        let month = OffsetDateTime::from_unix_timestamp(self.local_timestamp)
+            .ok()?
+            .month();
+        let wind_dir = self
+            .outdoor
+            .as_ref()
+            .filter(|x| x.wind_actual.0 > 0.0)
+            .map(|x| x.wind_dir);
+        let hemisphere = match station.is_northern_hemisphere() {
+            true => Hemisphere::Northern,
+            false => Hemisphere::Southern,
+        };
+
+        Some(Zambretti::forecast(
+            self.indoor.baro_sea,
+            tendency.trend.into(),
+            wind_dir,
+            month,
+            hemisphere,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forecast() {
+        let forecast = Zambretti::forecast(
+            Hectopascal(1020.0),
+            ZambrettiTrend::Steady,
+            None,
+            Month::January,
+            Hemisphere::Northern,
+        );
+        assert_eq!('B', forecast.letter());
+        assert_eq!("Fine weather", forecast.text());
+
+        let forecast = Zambretti::forecast(
+            Hectopascal(1000.0),
+            ZambrettiTrend::Falling,
+            Some(Degrees(225)),
+            Month::January,
+            Hemisphere::Northern,
+        );
+        assert_eq!('X', forecast.letter());
+        assert!(!forecast.out_of_range);
+
+        let forecast = Zambretti::forecast(
+            Hectopascal(1010.0),
+            ZambrettiTrend::Rising,
+            Some(Degrees(0)),
+            Month::July,
+            Hemisphere::Northern,
+        );
+        assert_eq!("Settled fine", forecast.text());
+    }
+
+    #[test]
+    fn test_southern_hemisphere() {
+        let north = Zambretti::forecast(
+            Hectopascal(1000.0),
+            ZambrettiTrend::Falling,
+            Some(Degrees(45)),
+            Month::January,
+            Hemisphere::Northern,
+        );
+        let south = Zambretti::forecast(
+            Hectopascal(1000.0),
+            ZambrettiTrend::Falling,
+            Some(Degrees(225)),
+            Month::July,
+            Hemisphere::Southern,
+        );
+        assert_eq!(north, south);
+
+        let forecast = Zambretti::forecast(
+            Hectopascal(940.0),
+            ZambrettiTrend::Steady,
+            None,
+            Month::July,
+            Hemisphere::Southern,
+        );
+        assert_eq!('Z', forecast.letter());
+        assert!(forecast.out_of_range);
+    }
+}
@@ -16,24 +16,46 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 \******************************************************************************/
 
-use super::Ws6in1Data;
+use super::{Ws6in1Data, Ws6in1ParseConfig, Ws6in1ParseWarnings};
 use crate::{protocol::Ws6in1DataFrameBase, Error, Ws6in1Container};
 
 /// Holds state for message fragment assembly.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
-pub struct Ws6in1AssemblerBase<V> {
+pub struct Ws6in1AssemblerBase<'a, V> {
     /// Current fragment number.
     frag_idx: u8,
     /// Message assembly buffer.
     buffer: V,
+    /// Configuration used to parse assembled messages.
+    config: Ws6in1ParseConfig<'a>,
+    /// Warnings found while parsing the last assembled message.
+    warnings: Ws6in1ParseWarnings,
 }
 
-impl<V> Ws6in1AssemblerBase<V> {
+impl<V> Ws6in1AssemblerBase<'_, V> {
     /// Maximum supported assembled message length.
     pub const MAX_MESSAGE_LEN: usize = 256;
 }
 
-impl<V: Ws6in1Container<u8>> Ws6in1AssemblerBase<V> {
+impl<'a, V: Default> Ws6in1AssemblerBase<'a, V> {
+    /// Creates an assembler which parses messages with the given
+    /// configuration.
+    pub fn with_config(config: Ws6in1ParseConfig<'a>) -> Self {
+        Self {
+            frag_idx: 0,
+            buffer: V::default(),
+            config,
+            warnings: Ws6in1ParseWarnings::default(),
+        }
+    }
+
+    /// Returns the warnings found while parsing the last assembled message.
+    pub fn warnings(&self) -> &Ws6in1ParseWarnings {
+        &self.warnings
+    }
+}
+
+impl<V: Ws6in1Container<u8>> Ws6in1AssemblerBase<'_, V> {
     /// Adds a message fragment to the internal buffer.
     /// If the full message was received, is parses the data and returns it.
     /// If the full message is not received yet, [None] is returned.
@@ -50,15 +72,20 @@ impl<V: Ws6in1Container<u8>> Ws6in1AssemblerBase<V> {
             let idx = self.frag_idx;
             self.frag_idx = 0;
             self.buffer.clear();
+            self.warnings = Ws6in1ParseWarnings::default();
             return Err(Error::FragmentDiscarded { idx });
         }
 
         if self.frag_idx == packet.hdr.frag_cnt {
-            let data = core::str::from_utf8(&self.buffer)
-                .map_err(|e| Error::InvalidCharacter {
+            self.warnings = Ws6in1ParseWarnings::default();
+            let msg = core::str::from_utf8(&self.buffer).map_err(|e| {
+                Error::InvalidCharacter {
                     idx: e.valid_up_to(),
-                })?
-                .try_into()?;
+                }
+            })?;
+            let (data, warnings) =
+                Ws6in1Data::parse_with_warnings(msg, &self.config)?;
+            self.warnings = warnings;
             self.frag_idx = 0;
             self.buffer.clear();
 
@@ -71,19 +98,20 @@ impl<V: Ws6in1Container<u8>> Ws6in1AssemblerBase<V> {
 
 #[cfg(feature = "std")]
 /// A [Ws6in1AssemblerBase] using std [Vec] as storage.
-pub type Ws6in1AssemblerStd = Ws6in1AssemblerBase<Vec<u8>>;
+pub type Ws6in1AssemblerStd<'a> = Ws6in1AssemblerBase<'a, Vec<u8>>;
 #[cfg(feature = "heapless")]
 /// A [Ws6in1AssemblerBase] using [heapless::Vec] as storage.
-pub type Ws6in1AssemblerHeapless = Ws6in1AssemblerBase<
+pub type Ws6in1AssemblerHeapless<'a> = Ws6in1AssemblerBase<
+    'a,
     heapless::Vec<u8, { Ws6in1AssemblerBase::<()>::MAX_MESSAGE_LEN }>,
 >;
 
 #[cfg(feature = "std")]
 /// A [Ws6in1AssemblerBase] using default storage based on selected features.
-pub type Ws6in1Assembler = Ws6in1AssemblerStd;
+pub type Ws6in1Assembler<'a> = Ws6in1AssemblerStd<'a>;
 #[cfg(not(feature = "std"))]
 /// A [Ws6in1AssemblerBase] using default storage based on selected features.
-pub type Ws6in1Assembler = Ws6in1AssemblerHeapless;
+pub type Ws6in1Assembler<'a> = Ws6in1AssemblerHeapless<'a>;
 
 #[cfg(test)]
 mod tests {
@@ -91,10 +119,17 @@ mod tests {
 
     use super::*;
     use crate::{
-        parser::{Ws6in1ExtData, Ws6in1IndoorData, Ws6in1OutdoorData},
+        parser::{
+            ParseMode, Ws6in1ExtData, Ws6in1IndoorData, Ws6in1OutdoorData,
+            Ws6in1Quality,
+        },
         protocol::{
             Ws6in1DataFrameHeapless, Ws6in1DataHeader, Ws6in1PayloadHeapless,
         },
+        units::{
+            Celsius, Degrees, Hectopascal, MetersPerSecond, Millimeters,
+            Percent, UvIndex, Ws6in1Units,
+        },
     };
 
     #[test]
@@ -141,30 +176,30 @@ mod tests {
         let expected = Ws6in1Data {
             local_timestamp: 1579282200,
             indoor: Ws6in1IndoorData {
-                temperature: 20.4,
-                humidity: 49,
-                baro_sea: 1017,
-                baro_absolute: 954,
+                temperature: Celsius(20.4),
+                humidity: Percent(49),
+                baro_sea: Hectopascal(1017.0),
+                baro_absolute: Hectopascal(954.0),
             },
             outdoor: Some(Ws6in1OutdoorData {
-                temperature: 6.0,
-                humidity: 60,
-                rain_day: 0.0,
-                rain_actual: 0.0,
-                wind_actual: 0.0,
-                wind_gust: 0.0,
-                wind_dir: 129,
-                uv_index: 0.0,
-                dew_point: -1.2,
+                temperature: Celsius(6.0),
+                humidity: Percent(60),
+                rain_day: Millimeters(0.0),
+                rain_actual: Millimeters(0.0),
+                wind_actual: MetersPerSecond(0.0),
+                wind_gust: MetersPerSecond(0.0),
+                wind_dir: Degrees(129),
+                uv_index: UvIndex(0.0),
+                dew_point: Celsius(-1.2),
             }),
             ext: [
                 Some(Ws6in1ExtData {
-                    temperature: 27.3,
-                    humidity: 57,
+                    temperature: Celsius(27.3),
+                    humidity: Percent(57),
                 }),
                 Some(Ws6in1ExtData {
-                    temperature: 33.4,
-                    humidity: 40,
+                    temperature: Celsius(33.4),
+                    humidity: Percent(40),
                 }),
                 None,
                 None,
@@ -172,6 +207,9 @@ mod tests {
                 None,
                 None,
             ],
+            console_units: Ws6in1Units::default(),
+            received: None,
+            quality: Ws6in1Quality::default(),
         };
 
         assert!(asm.parse(frame1).unwrap().is_none());
@@ -214,4 +252,49 @@ mod tests {
         asm.parse(frame2)
             .expect_err("Invalid packet sequence did not trigger restart");
     }
+
+    #[test]
+    fn test_warnings_cleared_on_error() {
+        let mut asm = Ws6in1AssemblerHeapless::with_config(Ws6in1ParseConfig {
+            mode: ParseMode::Lenient,
+            ..Default::default()
+        });
+        let frame = |frag_cnt, frag_idx, data: &[u8]| Ws6in1DataFrameHeapless {
+            hdr: Ws6in1DataHeader {
+                frag_cnt,
+                frag_idx,
+                ..Default::default()
+            },
+            payload: Ws6in1PayloadHeapless {
+                data: Vec::from_slice(data).unwrap(),
+            },
+        };
+
+        let parse_lenient = |asm: &mut Ws6in1AssemblerHeapless| {
+            asm.parse(frame(
+                3,
+                1,
+                b"3 2020-01-17 17:30 20.4 49 6.0 160 0.0 0.0 0.0 0.0 129",
+            ))
+            .unwrap();
+            asm.parse(frame(
+                3,
+                2,
+                b" SE 1017 954 0 -1.2 --.- 27.3 57 33.4 40 --.- -- --.- ",
+            ))
+            .unwrap();
+            asm.parse(frame(3, 3, b"-- --.- -- --.- -- --.- --"))
+                .unwrap()
+                .unwrap();
+            assert_eq!(1, asm.warnings().len());
+        };
+
+        parse_lenient(&mut asm);
+        asm.parse(frame(3, 2, b"foo")).unwrap_err();
+        assert!(asm.warnings().is_empty());
+
+        parse_lenient(&mut asm);
+        asm.parse(frame(1, 1, b"foo")).unwrap_err();
+        assert!(asm.warnings().is_empty());
+    }
 }
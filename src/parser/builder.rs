@@ -0,0 +1,310 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use super::{
+    Ws6in1Data, Ws6in1ExtData, Ws6in1IndoorData, Ws6in1OutdoorData,
+    Ws6in1PlausibilityLimits,
+};
+use crate::{
+    units::{
+        Celsius, Degrees, Hectopascal, MetersPerSecond, Millimeters, Percent,
+        UvIndex, Ws6in1Units,
+    },
+    Error, Result,
+};
+
+/// Implements setters for single outdoor fields. Each setter marks the
+/// outdoor sensor available with default values for all other fields.
+macro_rules! outdoor_setters {
+    ($($(#[$meta:meta])* $setter:ident => $field:ident: $type:ty),+ $(,)?) => {
+        impl Ws6in1DataBuilder {
+            $(
+                $(#[$meta])*
+                pub fn $setter(mut self, value: $type) -> Self {
+                    self.data
+                        .outdoor
+                        .get_or_insert_with(Default::default)
+                        .$field = value;
+                    self
+                }
+            )+
+        }
+    };
+}
+
+/// Builder for [Ws6in1Data], mainly intended for tests and simulators.
+///
+/// By default, the indoor console reports 20 °C, 50 % and 1013.25 hPa
+/// while outdoor and extra sensors are unavailable.
+#[derive(Clone, Debug)]
+pub struct Ws6in1DataBuilder {
+    data: Ws6in1Data,
+    limits: Ws6in1PlausibilityLimits,
+    invalid_channel: Option<usize>,
+}
+
+impl Default for Ws6in1DataBuilder {
+    fn default() -> Self {
+        Self {
+            data: Ws6in1Data {
+                indoor: Ws6in1IndoorData {
+                    temperature: Celsius(20.0),
+                    humidity: Percent(50),
+                    baro_sea: Hectopascal(1013.25),
+                    baro_absolute: Hectopascal(1013.25),
+                },
+                ..Default::default()
+            },
+            limits: Ws6in1PlausibilityLimits::default(),
+            invalid_channel: None,
+        }
+    }
+}
+
+impl Ws6in1DataBuilder {
+    /// Creates a new builder with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the raw local unix timestamp.
+    pub fn local_timestamp(mut self, timestamp: i64) -> Self {
+        self.data.local_timestamp = timestamp;
+        self
+    }
+
+    /// Sets the consoles local wall clock time.
+    pub fn local_datetime(mut self, datetime: PrimitiveDateTime) -> Self {
+        self.data.local_timestamp = datetime.assume_utc().unix_timestamp();
+        self
+    }
+
+    /// Sets the consoles local wall clock time from a time with offset.
+    /// The offset is discarded like the console does.
+    pub fn local_offset_datetime(self, datetime: OffsetDateTime) -> Self {
+        self.local_datetime(PrimitiveDateTime::new(
+            datetime.date(),
+            datetime.time(),
+        ))
+    }
+
+    /// Sets the host receive time.
+    pub fn received(mut self, received: OffsetDateTime) -> Self {
+        self.data.received = Some(received);
+        self
+    }
+
+    /// Sets the units the console reported in.
+    pub fn console_units(mut self, units: Ws6in1Units) -> Self {
+        self.data.console_units = units;
+        self
+    }
+
+    /// Sets all indoor data.
+    pub fn indoor(mut self, indoor: Ws6in1IndoorData) -> Self {
+        self.data.indoor = indoor;
+        self
+    }
+
+    /// Sets the indoor temperature and humidity.
+    pub fn indoor_climate(
+        mut self,
+        temperature: Celsius,
+        humidity: Percent,
+    ) -> Self {
+        self.data.indoor.temperature = temperature;
+        self.data.indoor.humidity = humidity;
+        self
+    }
+
+    /// Sets the sea level and absolute pressure.
+    pub fn pressure(
+        mut self,
+        baro_sea: Hectopascal,
+        baro_absolute: Hectopascal,
+    ) -> Self {
+        self.data.indoor.baro_sea = baro_sea;
+        self.data.indoor.baro_absolute = baro_absolute;
+        self
+    }
+
+    /// Sets all outdoor data and marks the outdoor sensor available.
+    pub fn outdoor(mut self, outdoor: Ws6in1OutdoorData) -> Self {
+        self.data.outdoor = Some(outdoor);
+        self
+    }
+
+    /// Marks the outdoor sensor unavailable.
+    pub fn no_outdoor(mut self) -> Self {
+        self.data.outdoor = None;
+        self
+    }
+
+    /// Sets the data of extra sensor channel `idx`, starting at zero.
+    pub fn ext(mut self, idx: usize, ext: Ws6in1ExtData) -> Self {
+        match self.data.ext.get_mut(idx) {
+            Some(channel) => *channel = Some(ext),
+            None => self.invalid_channel = Some(idx),
+        }
+        self
+    }
+
+    /// Sets temperature and humidity of extra sensor channel `idx`.
+    pub fn ext_climate(
+        self,
+        idx: usize,
+        temperature: Celsius,
+        humidity: Percent,
+    ) -> Self {
+        self.ext(
+            idx,
+            Ws6in1ExtData {
+                temperature,
+                humidity,
+            },
+        )
+    }
+
+    /// Marks extra sensor channel `idx` unavailable.
+    pub fn no_ext(mut self, idx: usize) -> Self {
+        match self.data.ext.get_mut(idx) {
+            Some(channel) => *channel = None,
+            None => self.invalid_channel = Some(idx),
+        }
+        self
+    }
+
+    /// Sets the limits used for validation at [Self::build].
+    pub fn limits(mut self, limits: Ws6in1PlausibilityLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Validates and returns the data. Implausible values result in an
+    /// [Error::ImplausibleValue] for the first suspect field.
+    pub fn build(self) -> Result<Ws6in1Data> {
+        let data = self.build_unchecked()?;
+        let suspect = data.quality.suspect_fields().next();
+        match suspect {
+            Some(field) => Err(Error::ImplausibleValue { field }),
+            None => Ok(data),
+        }
+    }
+
+    /// Returns the data with quality flags set but without rejecting
+    /// implausible values.
+    pub fn build_unchecked(self) -> Result<Ws6in1Data> {
+        if let Some(channel) = self.invalid_channel {
+            return Err(Error::InvalidChannel { channel });
+        }
+
+        let mut data = self.data;
+        data.quality = data.validate_with(&self.limits);
+        Ok(data)
+    }
+}
+
+outdoor_setters! {
+    /// Sets the outdoor temperature.
+    outdoor_temperature => temperature: Celsius,
+    /// Sets the outdoor relative humidity.
+    outdoor_humidity => humidity: Percent,
+    /// Sets the rain since the consoles midnight.
+    rain_day => rain_day: Millimeters,
+    /// Sets the current rain amount.
+    rain_actual => rain_actual: Millimeters,
+    /// Sets the current average wind speed.
+    wind_actual => wind_actual: MetersPerSecond,
+    /// Sets the current wind gust speed.
+    wind_gust => wind_gust: MetersPerSecond,
+    /// Sets the wind direction.
+    wind_dir => wind_dir: Degrees,
+    /// Sets the current UV index.
+    uv_index => uv_index: UvIndex,
+    /// Sets the outdoor dew point.
+    dew_point => dew_point: Celsius,
+}
+
+impl Ws6in1Data {
+    /// Returns a builder with default values.
+    pub fn builder() -> Ws6in1DataBuilder {
+        Ws6in1DataBuilder::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::parser::Field;
+
+    #[test]
+    fn test_build_data() {
+        let data = Ws6in1Data::builder()
+            .local_datetime(datetime!(2020-01-17 17:30))
+            .indoor_climate(Celsius(21.5), Percent(45))
+            .outdoor_temperature(Celsius(4.0))
+            .outdoor_humidity(Percent(80))
+            .wind_dir(Degrees(270))
+            .ext_climate(2, Celsius(12.0), Percent(70))
+            .build()
+            .unwrap();
+
+        assert_eq!(1579282200, data.local_timestamp);
+        assert_eq!(Celsius(21.5), data.indoor.temperature);
+        assert_eq!(Hectopascal(1013.25), data.indoor.baro_sea);
+        let outdoor = data.outdoor.unwrap();
+        assert_eq!(Celsius(4.0), outdoor.temperature);
+        assert_eq!(Percent(80), outdoor.humidity);
+        assert_eq!(Degrees(270), outdoor.wind_dir);
+        assert_eq!(Millimeters(0.0), outdoor.rain_day);
+        assert_eq!(None, data.ext[1]);
+        assert_eq!(Percent(70), data.ext[2].unwrap().humidity);
+        assert!(data.quality.is_plausible());
+    }
+
+    #[test]
+    fn test_build_implausible_data() {
+        let builder = Ws6in1Data::builder()
+            .outdoor_temperature(Celsius(4.0))
+            .wind_actual(MetersPerSecond(5.0))
+            .wind_gust(MetersPerSecond(3.0));
+
+        match builder.clone().build() {
+            Err(
+                e @ Error::ImplausibleValue {
+                    field: Field::WindGust,
+                },
+            ) => assert!(!e.is_transient()),
+            x => panic!("Unexpected result {x:?}"),
+        }
+        let data = builder.build_unchecked().unwrap();
+        assert!(data.quality.is_suspect(Field::WindGust));
+    }
+
+    #[test]
+    fn test_build_invalid_channel() {
+        let result = Ws6in1Data::builder()
+            .ext_climate(7, Celsius(12.0), Percent(70))
+            .build();
+        assert!(matches!(result, Err(Error::InvalidChannel { channel: 7 })));
+    }
+}
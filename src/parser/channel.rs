@@ -0,0 +1,153 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use super::{Ws6in1Data, Ws6in1ExtData};
+use crate::units::{Percent, TemperatureDifference};
+
+/// Calibration applied to the readings of an extra sensor.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Calibration {
+    /// Offset added to the measured temperature.
+    pub temperature_offset: TemperatureDifference,
+    /// Offset in percent points added to the measured humidity.
+    /// The result is limited to 0 to 100 %.
+    pub humidity_offset: i8,
+}
+
+impl Calibration {
+    /// Returns the calibrated sensor data.
+    pub fn apply(&self, data: &Ws6in1ExtData) -> Ws6in1ExtData {
+        let humidity = (i16::from(data.humidity.0)
+            + i16::from(self.humidity_offset))
+        .clamp(0, 100);
+
+        Ws6in1ExtData {
+            temperature: data.temperature + self.temperature_offset,
+            humidity: Percent(humidity as u8),
+        }
+    }
+}
+
+/// User configuration of a single extra sensor channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Ws6in1Channel<'a> {
+    /// Display name of the channel, e.g. "Greenhouse".
+    pub name: Option<&'a str>,
+    /// Location of the sensor, e.g. "Garden".
+    pub location: Option<&'a str>,
+    /// Calibration applied to the sensor readings.
+    pub calibration: Option<Calibration>,
+}
+
+impl Ws6in1Channel<'_> {
+    /// Returns the sensor data with calibration applied.
+    pub fn calibrate(&self, data: &Ws6in1ExtData) -> Ws6in1ExtData {
+        match &self.calibration {
+            Some(calibration) => calibration.apply(data),
+            None => *data,
+        }
+    }
+}
+
+/// User configuration of all extra sensor channels.
+///
+/// Channels are addressed by their zero based index into
+/// [Ws6in1Data::ext]. The console displays the channel with index `i` as
+/// channel `i + 1`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Ws6in1ChannelConfig<'a> {
+    pub channels: [Ws6in1Channel<'a>; Ws6in1Data::EXT_SENSOR_COUNT],
+}
+
+impl<'a> Ws6in1ChannelConfig<'a> {
+    /// Returns the configuration of the channel with the given index.
+    pub fn channel(&self, idx: usize) -> Option<&Ws6in1Channel<'a>> {
+        self.channels.get(idx)
+    }
+
+    /// Returns the index of the first channel with the given name.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.channels.iter().position(|x| x.name == Some(name))
+    }
+
+    /// Iterates over all present extra sensors of a reading and yields
+    /// the channel index, its configuration and the calibrated data.
+    pub fn iter<'b>(
+        &'b self,
+        data: &'b Ws6in1Data,
+    ) -> impl Iterator<Item = (usize, &'b Ws6in1Channel<'a>, Ws6in1ExtData)> + 'b
+    {
+        data.ext_iter().map(|(idx, ext)| {
+            let channel = &self.channels[idx];
+            (idx, channel, channel.calibrate(ext))
+        })
+    }
+}
+
+impl Ws6in1Data {
+    /// Iterates over all present extra sensors and yields their channel
+    /// index and data.
+    pub fn ext_iter(&self) -> impl Iterator<Item = (usize, &Ws6in1ExtData)> {
+        self.ext
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, ext)| ext.as_ref().map(|x| (idx, x)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::Celsius;
+
+    #[test]
+    fn test_channel_iteration() {
+        let mut data = Ws6in1Data::default();
+        data.ext[0] = Some(Ws6in1ExtData {
+            temperature: Celsius(18.6),
+            humidity: Percent(52),
+        });
+        data.ext[2] = Some(Ws6in1ExtData {
+            temperature: Celsius(22.0),
+            humidity: Percent(98),
+        });
+
+        let mut config = Ws6in1ChannelConfig::default();
+        config.channels[2] = Ws6in1Channel {
+            name: Some("Greenhouse"),
+            location: Some("Garden"),
+            calibration: Some(Calibration {
+                temperature_offset: TemperatureDifference(-0.5),
+                humidity_offset: 5,
+            }),
+        };
+
+        assert_eq!(Some(2), config.find("Greenhouse"));
+        assert_eq!(None, config.find("Cellar"));
+
+        let channels: Vec<_> = config.iter(&data).collect();
+        assert_eq!(2, channels.len());
+        assert_eq!(0, channels[0].0);
+        assert_eq!(None, channels[0].1.name);
+        assert_eq!(Celsius(18.6), channels[0].2.temperature);
+        assert_eq!(2, channels[1].0);
+        assert_eq!(Some("Greenhouse"), channels[1].1.name);
+        assert_eq!(Celsius(21.5), channels[1].2.temperature);
+        assert_eq!(Percent(100), channels[1].2.humidity);
+    }
+}
@@ -0,0 +1,119 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use super::{LayoutSelection, ParseMode};
+use crate::units::{
+    Hectopascal, PrecipitationUnit, PressureUnit, SpeedUnit, TemperatureUnit,
+    Unit,
+};
+
+/// Selects how the unit of a quantity in a message is determined.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum UnitSetting<U> {
+    /// The console is known to use the given unit.
+    Fixed(U),
+    /// The unit is detected from value ranges and decimal patterns.
+    Detect,
+}
+
+impl<U: Unit> Default for UnitSetting<U> {
+    fn default() -> Self {
+        Self::Fixed(U::default())
+    }
+}
+
+impl<U: Unit> UnitSetting<U> {
+    pub(crate) fn resolve(self, detect: impl FnOnce() -> U) -> U {
+        match self {
+            Self::Fixed(unit) => unit,
+            Self::Detect => detect(),
+        }
+    }
+}
+
+/// Configuration of the message parser.
+///
+/// The console formats the values in a message according to its display
+/// settings. The parser converts all values to the canonical metric units
+/// and records the units the console sent in
+/// [Ws6in1Data::console_units](super::Ws6in1Data::console_units).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Ws6in1ParseConfig<'a> {
+    /// Temperature unit. Detection assumes °F if the indoor temperature
+    /// is above 40.
+    pub temperature: UnitSetting<TemperatureUnit>,
+    /// Pressure unit. Detection assumes inHg for values with decimals,
+    /// mmHg for sea level pressures below 850 and hPa otherwise.
+    pub pressure: UnitSetting<PressureUnit>,
+    /// Wind speed unit. It can not be detected from the message.
+    pub speed: SpeedUnit,
+    /// Precipitation unit. Detection assumes inches for values with
+    /// two decimals.
+    pub precipitation: UnitSetting<PrecipitationUnit>,
+    /// Message layout.
+    pub layout: LayoutSelection<'a>,
+    /// Handling of malformed messages.
+    pub mode: ParseMode,
+}
+
+impl Ws6in1ParseConfig<'_> {
+    /// Creates a configuration that detects all detectable units and
+    /// assumes the given wind speed unit.
+    pub fn detect(speed: SpeedUnit) -> Self {
+        Self {
+            temperature: UnitSetting::Detect,
+            pressure: UnitSetting::Detect,
+            speed,
+            precipitation: UnitSetting::Detect,
+            layout: LayoutSelection::default(),
+            mode: ParseMode::default(),
+        }
+    }
+}
+
+/// Indoor temperatures above this value are implausible in °C but
+/// plausible in °F.
+const DETECT_FAHRENHEIT_ABOVE: f32 = 40.0;
+/// Sea level pressures below this value are implausible in hPa but
+/// plausible in mmHg.
+const DETECT_MMHG_BELOW: Hectopascal = Hectopascal(850.0);
+
+pub(crate) fn detect_temperature(indoor: f32) -> TemperatureUnit {
+    if indoor > DETECT_FAHRENHEIT_ABOVE {
+        TemperatureUnit::Fahrenheit
+    } else {
+        TemperatureUnit::Celsius
+    }
+}
+
+pub(crate) fn detect_pressure(token: &str, value: f32) -> PressureUnit {
+    if token.contains('.') {
+        PressureUnit::InchesOfMercury
+    } else if value < DETECT_MMHG_BELOW.0 {
+        PressureUnit::MillimetersOfMercury
+    } else {
+        PressureUnit::Hectopascal
+    }
+}
+
+pub(crate) fn detect_precipitation(token: &str) -> PrecipitationUnit {
+    match token.split_once('.') {
+        Some((_, decimals)) if decimals.len() >= 2 => PrecipitationUnit::Inches,
+        _ => PrecipitationUnit::Millimeters,
+    }
+}
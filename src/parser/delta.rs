@@ -0,0 +1,343 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use time::Duration;
+
+use super::{Field, Ws6in1Data};
+use crate::units::Millimeters;
+
+/// A sensor which may appear or disappear between readings.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Sensor {
+    /// The main outdoor sensor.
+    Outdoor,
+    /// The extra sensor with the given zero based channel index.
+    Ext(u8),
+}
+
+impl Sensor {
+    fn bit(self) -> u8 {
+        match self {
+            Self::Outdoor => 1,
+            Self::Ext(idx) => 2 << idx,
+        }
+    }
+}
+
+/// Change of a single field between two readings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FieldChange {
+    /// The changed field.
+    pub field: Field,
+    /// Value in the previous reading in canonical units.
+    pub previous: f32,
+    /// Value in the current reading in canonical units.
+    pub current: f32,
+}
+
+impl FieldChange {
+    /// Returns the signed difference. Wind direction changes are
+    /// returned as shortest rotation within -180..=180 degrees.
+    pub fn difference(&self) -> f32 {
+        let difference = self.current - self.previous;
+        if self.field != Field::WindDir {
+            difference
+        } else if difference > 180.0 {
+            difference - 360.0
+        } else if difference < -180.0 {
+            difference + 360.0
+        } else {
+            difference
+        }
+    }
+}
+
+/// Changes between two readings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ws6in1Delta {
+    /// Time between both readings based on the console time.
+    pub elapsed: Duration,
+    /// True if the daily rain counter was reset between both readings.
+    pub rain_reset: bool,
+    changes: [Option<FieldChange>; FIELDS.len()],
+    len: usize,
+    appeared: u8,
+    disappeared: u8,
+}
+
+/// All fields containing a measured value.
+const FIELDS: [Field; 27] = [
+    Field::IndoorTemperature,
+    Field::IndoorHumidity,
+    Field::BaroSea,
+    Field::BaroAbsolute,
+    Field::OutdoorTemperature,
+    Field::OutdoorHumidity,
+    Field::RainDay,
+    Field::RainActual,
+    Field::WindActual,
+    Field::WindGust,
+    Field::WindDir,
+    Field::UvIndex,
+    Field::DewPoint,
+    Field::ExtTemperature(0),
+    Field::ExtHumidity(0),
+    Field::ExtTemperature(1),
+    Field::ExtHumidity(1),
+    Field::ExtTemperature(2),
+    Field::ExtHumidity(2),
+    Field::ExtTemperature(3),
+    Field::ExtHumidity(3),
+    Field::ExtTemperature(4),
+    Field::ExtHumidity(4),
+    Field::ExtTemperature(5),
+    Field::ExtHumidity(5),
+    Field::ExtTemperature(6),
+    Field::ExtHumidity(6),
+];
+
+impl Ws6in1Delta {
+    /// Returns true if nothing but the time changed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0 && self.appeared == 0 && self.disappeared == 0
+    }
+
+    /// Iterates over all changed fields of sensors present in both
+    /// readings.
+    pub fn changes(&self) -> impl Iterator<Item = &FieldChange> {
+        self.changes[..self.len].iter().flatten()
+    }
+
+    /// Returns the change of the given field if it changed.
+    pub fn change(&self, field: Field) -> Option<&FieldChange> {
+        self.changes().find(|x| x.field == field)
+    }
+
+    /// Iterates over all sensors missing in the previous reading.
+    pub fn appeared(&self) -> impl Iterator<Item = Sensor> + '_ {
+        Self::sensors(self.appeared)
+    }
+
+    /// Iterates over all sensors missing in the current reading.
+    pub fn disappeared(&self) -> impl Iterator<Item = Sensor> + '_ {
+        Self::sensors(self.disappeared)
+    }
+
+    fn sensors(mask: u8) -> impl Iterator<Item = Sensor> {
+        core::iter::once(Sensor::Outdoor)
+            .chain((0..Ws6in1Data::EXT_SENSOR_COUNT as u8).map(Sensor::Ext))
+            .filter(move |x| mask & x.bit() != 0)
+    }
+}
+
+impl Ws6in1Data {
+    /// Returns the value of the given field in canonical units or [None]
+    /// if the field has no value or its sensor is unavailable.
+    pub fn value(&self, field: Field) -> Option<f32> {
+        let indoor = &self.indoor;
+        let outdoor = self.outdoor.as_ref();
+        let ext = |idx: u8| self.ext.get(idx as usize).copied().flatten();
+
+        match field {
+            Field::IndoorTemperature => Some(indoor.temperature.0),
+            Field::IndoorHumidity => Some(f32::from(indoor.humidity.0)),
+            Field::BaroSea => Some(indoor.baro_sea.0),
+            Field::BaroAbsolute => Some(indoor.baro_absolute.0),
+            Field::OutdoorTemperature => outdoor.map(|x| x.temperature.0),
+            Field::OutdoorHumidity => outdoor.map(|x| f32::from(x.humidity.0)),
+            Field::RainDay => outdoor.map(|x| x.rain_day.0),
+            Field::RainActual => outdoor.map(|x| x.rain_actual.0),
+            Field::WindActual => outdoor.map(|x| x.wind_actual.0),
+            Field::WindGust => outdoor.map(|x| x.wind_gust.0),
+            Field::WindDir => outdoor.map(|x| f32::from(x.wind_dir.0)),
+            Field::UvIndex => outdoor.map(|x| x.uv_index.0),
+            Field::DewPoint => outdoor.map(|x| x.dew_point.0),
+            Field::ExtTemperature(idx) => ext(idx).map(|x| x.temperature.0),
+            Field::ExtHumidity(idx) => {
+                ext(idx).map(|x| f32::from(x.humidity.0))
+            }
+            _ => None,
+        }
+    }
+
+    /// Computes the changes from the `previous` reading to this one.
+    pub fn delta(&self, previous: &Self) -> Ws6in1Delta {
+        let mut delta = Ws6in1Delta {
+            elapsed: Duration::seconds(
+                self.local_timestamp - previous.local_timestamp,
+            ),
+            rain_reset: self.rain_reset_since(previous),
+            changes: [None; FIELDS.len()],
+            len: 0,
+            appeared: 0,
+            disappeared: 0,
+        };
+
+        for field in FIELDS {
+            if let (Some(previous), Some(current)) =
+                (previous.value(field), self.value(field))
+            {
+                if previous != current {
+                    delta.changes[delta.len] = Some(FieldChange {
+                        field,
+                        previous,
+                        current,
+                    });
+                    delta.len += 1;
+                }
+            }
+        }
+
+        for sensor in Ws6in1Delta::sensors(u8::MAX) {
+            match (previous.has_sensor(sensor), self.has_sensor(sensor)) {
+                (false, true) => delta.appeared |= sensor.bit(),
+                (true, false) => delta.disappeared |= sensor.bit(),
+                _ => (),
+            }
+        }
+
+        delta
+    }
+
+    /// Returns the rain amount since the `previous` reading, taking
+    /// resets of the daily counter into account. Rain between the
+    /// previous reading and a reset is lost. Returns [None] if the
+    /// outdoor sensor is unavailable in either reading.
+    pub fn rain_since(&self, previous: &Self) -> Option<Millimeters> {
+        let current = self.outdoor.as_ref()?.rain_day;
+        let last = previous.outdoor.as_ref()?.rain_day;
+        Some(rain_increment(
+            (previous.local_timestamp, last),
+            (self.local_timestamp, current),
+        ))
+    }
+
+    fn has_sensor(&self, sensor: Sensor) -> bool {
+        match sensor {
+            Sensor::Outdoor => self.outdoor.is_some(),
+            Sensor::Ext(idx) => {
+                self.ext.get(idx as usize).is_some_and(Option::is_some)
+            }
+        }
+    }
+
+    fn rain_reset_since(&self, previous: &Self) -> bool {
+        match (&self.outdoor, &previous.outdoor) {
+            (Some(current), Some(last)) => rain_reset(
+                (previous.local_timestamp, last.rain_day),
+                (self.local_timestamp, current.rain_day),
+            ),
+            _ => !same_day(previous.local_timestamp, self.local_timestamp),
+        }
+    }
+}
+
+fn same_day(previous: i64, current: i64) -> bool {
+    const DAY: i64 = 86400;
+    previous.div_euclid(DAY) == current.div_euclid(DAY)
+}
+
+/// Returns true if the daily rain counter was reset between two samples
+/// of console local time and counter value.
+fn rain_reset(
+    previous: (i64, Millimeters),
+    current: (i64, Millimeters),
+) -> bool {
+    !same_day(previous.0, current.0) || current.1 < previous.1
+}
+
+/// Returns the rain between two samples of console local time and daily
+/// rain counter. Rain between the previous sample and a reset is lost.
+pub(crate) fn rain_increment(
+    previous: (i64, Millimeters),
+    current: (i64, Millimeters),
+) -> Millimeters {
+    if rain_reset(previous, current) {
+        current.1
+    } else {
+        Millimeters(current.1 .0 - previous.1 .0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::{
+        parser::Ws6in1OutdoorData,
+        units::{Celsius, Degrees, Percent},
+    };
+
+    fn reading(datetime: time::PrimitiveDateTime, rain: f32) -> Ws6in1Data {
+        Ws6in1Data::builder()
+            .local_datetime(datetime)
+            .outdoor(Ws6in1OutdoorData {
+                temperature: Celsius(5.0),
+                rain_day: Millimeters(rain),
+                wind_dir: Degrees(350),
+                ..Default::default()
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_delta() {
+        let previous = reading(datetime!(2020-01-17 17:30), 2.4);
+        let mut current = reading(datetime!(2020-01-17 17:32), 3.0);
+        current.indoor.humidity = Percent(52);
+        current.outdoor.as_mut().unwrap().wind_dir = Degrees(10);
+        current.ext[3] = Some(Default::default());
+
+        let delta = current.delta(&previous);
+        assert_eq!(Duration::minutes(2), delta.elapsed);
+        assert!(!delta.rain_reset);
+        assert_eq!(
+            vec![Field::IndoorHumidity, Field::RainDay, Field::WindDir],
+            delta.changes().map(|x| x.field).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            2.0,
+            delta.change(Field::IndoorHumidity).unwrap().difference()
+        );
+        assert_eq!(20.0, delta.change(Field::WindDir).unwrap().difference());
+        assert_eq!(vec![Sensor::Ext(3)], delta.appeared().collect::<Vec<_>>());
+        assert_eq!(0, delta.disappeared().count());
+        assert!(previous.delta(&previous).is_empty());
+    }
+
+    #[test]
+    fn test_delta_rain_reset() {
+        let previous = reading(datetime!(2020-01-17 23:58), 4.2);
+        let current = reading(datetime!(2020-01-18 00:01), 0.3);
+
+        let delta = current.delta(&previous);
+        assert!(delta.rain_reset);
+        assert_eq!(Some(Millimeters(0.3)), current.rain_since(&previous));
+
+        let mut missing = current.clone();
+        missing.outdoor = None;
+        let delta = missing.delta(&current);
+        assert_eq!(
+            vec![Sensor::Outdoor],
+            delta.disappeared().collect::<Vec<_>>()
+        );
+        assert_eq!(None, missing.rain_since(&current));
+    }
+}
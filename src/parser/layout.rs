@@ -0,0 +1,257 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use super::Value;
+use crate::{Error, Result};
+
+/// A field of a Ws6in1 message.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Field {
+    /// Fill level of the consoles history memory.
+    HistoryPct,
+    /// Local date of the reading.
+    Date,
+    /// Local time of the reading.
+    Time,
+    IndoorTemperature,
+    IndoorHumidity,
+    OutdoorTemperature,
+    OutdoorHumidity,
+    RainDay,
+    RainActual,
+    WindActual,
+    WindGust,
+    WindDir,
+    /// Wind direction as compass octant text.
+    WindOctant,
+    BaroSea,
+    BaroAbsolute,
+    UvIndex,
+    DewPoint,
+    /// A field with unknown meaning.
+    Unknown,
+    /// Temperature of the zero based extra sensor channel.
+    ExtTemperature(u8),
+    /// Humidity of the zero based extra sensor channel.
+    ExtHumidity(u8),
+}
+
+/// Syntax of a message token.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TokenType {
+    /// The token is skipped without being parsed.
+    Ignore,
+    /// A date in `YYYY-MM-DD` format.
+    Date,
+    /// A time in `HH:MM` format.
+    Time,
+    /// A decimal number.
+    Decimal,
+    /// An unsigned integer number.
+    Integer,
+}
+
+/// Describes how a missing value is encoded in a token.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Placeholder {
+    /// The token must always contain a valid value.
+    None,
+    /// Tokens only consisting of `-` and `.` mark a missing value.
+    Dashes,
+    /// Any unparsable token marks a missing value. In lenient mode, tokens
+    /// other than dashes are additionally reported as warning.
+    Any,
+}
+
+impl Placeholder {
+    pub(crate) fn matches(self, token: &str, lenient: bool) -> bool {
+        let dashes = || token.bytes().all(|x| x == b'-' || x == b'.');
+        match self {
+            Self::None => false,
+            Self::Dashes => dashes(),
+            Self::Any => !lenient || dashes(),
+        }
+    }
+}
+
+/// Description of a single whitespace separated message token.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Ws6in1Token {
+    /// Data field stored in this token.
+    pub field: Field,
+    /// Syntax of the token.
+    pub r#type: TokenType,
+    /// Encoding of missing values.
+    pub placeholder: Placeholder,
+}
+
+impl Ws6in1Token {
+    /// Creates a token that must always contain a valid value.
+    pub const fn required(field: Field, r#type: TokenType) -> Self {
+        Self {
+            field,
+            r#type,
+            placeholder: Placeholder::None,
+        }
+    }
+
+    /// Creates a token that contains a placeholder if the value is missing.
+    pub const fn optional(field: Field, r#type: TokenType) -> Self {
+        Self {
+            field,
+            r#type,
+            placeholder: Placeholder::Any,
+        }
+    }
+
+    /// Creates a token that is not parsed.
+    pub const fn ignored(field: Field) -> Self {
+        Self {
+            field,
+            r#type: TokenType::Ignore,
+            placeholder: Placeholder::Any,
+        }
+    }
+}
+
+/// Declarative description of the token sequence of a message.
+///
+/// Custom layouts for firmware variants can be selected through
+/// [LayoutSelection]. Extra sensor channels must be below
+/// [Ws6in1Data::EXT_SENSOR_COUNT](super::Ws6in1Data::EXT_SENSOR_COUNT).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Ws6in1Layout<'a> {
+    /// Human readable name of the layout.
+    pub name: &'a str,
+    /// Expected tokens in message order.
+    pub tokens: &'a [Ws6in1Token],
+}
+
+impl Ws6in1Layout<'static> {
+    /// Layout of the original CC8488 firmware with seven extra sensors.
+    pub const DEFAULT: Self = Self {
+        name: "CC8488",
+        tokens: &DEFAULT_TOKENS,
+    };
+}
+
+impl Ws6in1Layout<'_> {
+    /// Returns true if every token of the message is valid for its type
+    /// or a dashed placeholder.
+    fn accepts(&self, msg: &str) -> bool {
+        self.tokens
+            .iter()
+            .zip(msg.split_whitespace())
+            .all(|(token, text)| {
+                Value::parse(token, text).is_some()
+                    || token.placeholder.matches(text, true)
+            })
+    }
+}
+
+/// Selects the layout used to parse a message.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LayoutSelection<'a> {
+    /// All messages use the given layout.
+    Fixed(&'a Ws6in1Layout<'a>),
+    /// The layout with matching token count is used. If several layouts
+    /// have the same token count, the one whose token types match the
+    /// message is used. Messages matching several or none of those
+    /// layouts fail with [Error::AmbiguousLayout].
+    Detect(&'a [&'a Ws6in1Layout<'a>]),
+}
+
+impl Default for LayoutSelection<'_> {
+    fn default() -> Self {
+        Self::Fixed(&Ws6in1Layout::DEFAULT)
+    }
+}
+
+impl<'a> LayoutSelection<'a> {
+    pub(crate) fn select(
+        self,
+        msg: &str,
+    ) -> Result<Option<&'a Ws6in1Layout<'a>>> {
+        let layouts = match self {
+            Self::Fixed(layout) => return Ok(Some(layout)),
+            Self::Detect(layouts) => layouts,
+        };
+
+        let tokens = msg.split_whitespace().count();
+        let candidates = || {
+            layouts
+                .iter()
+                .copied()
+                .filter(move |x| x.tokens.len() == tokens)
+        };
+        let mut by_count = candidates();
+        match (by_count.next(), by_count.next()) {
+            (None, _) => return Ok(None),
+            (Some(layout), None) => return Ok(Some(layout)),
+            _ => (),
+        }
+
+        let mut by_type = candidates().filter(|x| x.accepts(msg));
+        match (by_type.next(), by_type.next()) {
+            (Some(layout), None) => Ok(Some(layout)),
+            _ => Err(Error::AmbiguousLayout { tokens }),
+        }
+    }
+
+    pub(crate) fn fallback(self) -> Option<&'a Ws6in1Layout<'a>> {
+        match self {
+            Self::Fixed(layout) => Some(layout),
+            Self::Detect(layouts) => layouts.first().copied(),
+        }
+    }
+}
+
+const DEFAULT_TOKENS: [Ws6in1Token; 32] = [
+    Ws6in1Token::ignored(Field::HistoryPct),
+    Ws6in1Token::required(Field::Date, TokenType::Date),
+    Ws6in1Token::required(Field::Time, TokenType::Time),
+    Ws6in1Token::required(Field::IndoorTemperature, TokenType::Decimal),
+    Ws6in1Token::required(Field::IndoorHumidity, TokenType::Integer),
+    Ws6in1Token::optional(Field::OutdoorTemperature, TokenType::Decimal),
+    Ws6in1Token::optional(Field::OutdoorHumidity, TokenType::Integer),
+    Ws6in1Token::optional(Field::RainDay, TokenType::Decimal),
+    Ws6in1Token::optional(Field::RainActual, TokenType::Decimal),
+    Ws6in1Token::optional(Field::WindActual, TokenType::Decimal),
+    Ws6in1Token::optional(Field::WindGust, TokenType::Decimal),
+    Ws6in1Token::optional(Field::WindDir, TokenType::Integer),
+    Ws6in1Token::ignored(Field::WindOctant),
+    Ws6in1Token::required(Field::BaroSea, TokenType::Decimal),
+    Ws6in1Token::required(Field::BaroAbsolute, TokenType::Decimal),
+    Ws6in1Token::optional(Field::UvIndex, TokenType::Decimal),
+    Ws6in1Token::optional(Field::DewPoint, TokenType::Decimal),
+    Ws6in1Token::ignored(Field::Unknown),
+    Ws6in1Token::optional(Field::ExtTemperature(0), TokenType::Decimal),
+    Ws6in1Token::optional(Field::ExtHumidity(0), TokenType::Integer),
+    Ws6in1Token::optional(Field::ExtTemperature(1), TokenType::Decimal),
+    Ws6in1Token::optional(Field::ExtHumidity(1), TokenType::Integer),
+    Ws6in1Token::optional(Field::ExtTemperature(2), TokenType::Decimal),
+    Ws6in1Token::optional(Field::ExtHumidity(2), TokenType::Integer),
+    Ws6in1Token::optional(Field::ExtTemperature(3), TokenType::Decimal),
+    Ws6in1Token::optional(Field::ExtHumidity(3), TokenType::Integer),
+    Ws6in1Token::optional(Field::ExtTemperature(4), TokenType::Decimal),
+    Ws6in1Token::optional(Field::ExtHumidity(4), TokenType::Integer),
+    Ws6in1Token::optional(Field::ExtTemperature(5), TokenType::Decimal),
+    Ws6in1Token::optional(Field::ExtHumidity(5), TokenType::Integer),
+    Ws6in1Token::optional(Field::ExtTemperature(6), TokenType::Decimal),
+    Ws6in1Token::optional(Field::ExtHumidity(6), TokenType::Integer),
+];
@@ -16,52 +16,91 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 \******************************************************************************/
 
-//! Ws6in1 message parser. Data is parsed through [TryFrom].
+//! Ws6in1 message parser. Data is parsed through [TryFrom] or
+//! [Ws6in1Data::parse] with a custom [Ws6in1ParseConfig].
 
 use core::str::SplitWhitespace;
 
 use time::{
     format_description::BorrowedFormatItem, macros::format_description, Date,
-    PrimitiveDateTime, Time,
+    OffsetDateTime, PrimitiveDateTime, Time,
 };
 
-use super::{Error, Result};
+use super::{Error, Result, Span, TokenText};
+use crate::units::{
+    Celsius, Degrees, Hectopascal, MetersPerSecond, Millimeters, Percent, Unit,
+    UvIndex, Ws6in1Units,
+};
 
 mod asm;
+mod builder;
+mod channel;
+mod config;
+mod delta;
+mod layout;
+mod timestamp;
+mod validate;
+mod warning;
 #[cfg(feature = "heapless")]
 pub use asm::Ws6in1AssemblerHeapless;
 #[cfg(feature = "std")]
 pub use asm::Ws6in1AssemblerStd;
 pub use asm::{Ws6in1Assembler, Ws6in1AssemblerBase};
+pub use builder::Ws6in1DataBuilder;
+pub use channel::{Calibration, Ws6in1Channel, Ws6in1ChannelConfig};
+pub use config::{UnitSetting, Ws6in1ParseConfig};
+pub(crate) use delta::rain_increment;
+pub use delta::{FieldChange, Sensor, Ws6in1Delta};
+pub use layout::{
+    Field, LayoutSelection, Placeholder, TokenType, Ws6in1Layout, Ws6in1Token,
+};
+pub use timestamp::DstPolicy;
+pub use validate::{Ws6in1PlausibilityLimits, Ws6in1Quality};
+pub use warning::{ParseMode, Ws6in1ParseWarning, Ws6in1ParseWarnings};
 
 /// Data from the indoor console.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Ws6in1IndoorData {
-    pub temperature: f32,
-    pub humidity: u8,
-    pub baro_sea: u16,
-    pub baro_absolute: u16,
+    /// Indoor temperature.
+    pub temperature: Celsius,
+    /// Indoor relative humidity.
+    pub humidity: Percent,
+    /// Console calculated sea level pressure.
+    pub baro_sea: Hectopascal,
+    /// Measured absolute pressure.
+    pub baro_absolute: Hectopascal,
 }
 
 /// Data from main outdoor sensor.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Ws6in1OutdoorData {
-    pub temperature: f32,
-    pub humidity: u8,
-    pub rain_day: f32,
-    pub rain_actual: f32,
-    pub wind_actual: f32,
-    pub wind_gust: f32,
-    pub wind_dir: u16,
-    pub uv_index: f32,
-    pub dew_point: f32,
+    /// Outdoor temperature.
+    pub temperature: Celsius,
+    /// Outdoor relative humidity.
+    pub humidity: Percent,
+    /// Rain since the consoles midnight.
+    pub rain_day: Millimeters,
+    /// Current rain amount.
+    pub rain_actual: Millimeters,
+    /// Current average wind speed.
+    pub wind_actual: MetersPerSecond,
+    /// Current wind gust speed.
+    pub wind_gust: MetersPerSecond,
+    /// Wind direction.
+    pub wind_dir: Degrees,
+    /// Current UV index.
+    pub uv_index: UvIndex,
+    /// Outdoor dew point.
+    pub dew_point: Celsius,
 }
 
 /// Data from an extra sensor.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Ws6in1ExtData {
-    pub temperature: f32,
-    pub humidity: u8,
+    /// Temperature at the extra sensor.
+    pub temperature: Celsius,
+    /// Relative humidity at the extra sensor.
+    pub humidity: Percent,
 }
 
 /// Parsed weather data from a Ws6in1 compatible weather station.
@@ -75,10 +114,16 @@ pub struct Ws6in1Data {
     pub outdoor: Option<Ws6in1OutdoorData>,
     /// Data measured by additional sensors.
     pub ext: [Option<Ws6in1ExtData>; Self::EXT_SENSOR_COUNT],
+    /// Units the console used in the parsed message.
+    pub console_units: Ws6in1Units,
+    /// Precise UTC time at which the host received this reading.
+    pub received: Option<OffsetDateTime>,
+    /// Plausibility of the individual fields.
+    pub quality: Ws6in1Quality,
 }
 
 impl Ws6in1Data {
-    /// Maximum amount of additional sensors.
+    /// Maximum amount of additional sensors supported by any layout.
     pub const EXT_SENSOR_COUNT: usize = 7;
     const DATE_FORMAT: &'static [BorrowedFormatItem<'_>] =
         format_description!("[year]-[month]-[day]");
@@ -86,92 +131,309 @@ impl Ws6in1Data {
         format_description!("[hour]:[minute]");
 }
 
+/// Splits a message into whitespace separated tokens and tracks their
+/// byte positions.
 struct TokenIterator<'a> {
+    msg: &'a str,
     iter: SplitWhitespace<'a>,
-    tpos: usize,
 }
 
 impl<'a> TokenIterator<'a> {
-    fn new(iter: SplitWhitespace<'a>) -> Self {
-        Self { iter, tpos: 0 }
+    fn new(msg: &'a str) -> Self {
+        Self {
+            msg,
+            iter: msg.split_whitespace(),
+        }
     }
 
-    fn pos(&self) -> usize {
-        self.tpos
+    fn span(&self, token: &str) -> Span {
+        // Tokens are always sub slices of the message.
+        let start = token.as_ptr() as usize - self.msg.as_ptr() as usize;
+        Span {
+            start,
+            end: start + token.len(),
+        }
     }
 
-    fn next(&mut self) -> Result<&str> {
-        let str = self
-            .iter
-            .next()
-            .ok_or(Error::UnexpectedEnd { tpos: self.tpos })?;
-        self.tpos += 1;
+    fn next(&mut self, field: Field) -> Result<(&'a str, Span)> {
+        let token = self.iter.next().ok_or(Error::UnexpectedEnd {
+            field,
+            offset: self.msg.trim_end().len(),
+        })?;
 
-        Ok(str)
+        Ok((token, self.span(token)))
+    }
+
+    fn remaining(&mut self) -> usize {
+        self.iter.by_ref().count()
     }
 
     fn end(&mut self) -> Result<()> {
         match self.iter.next() {
             None => Ok(()),
             Some(x) => Err(Error::GarbageEnd {
-                char: x.as_bytes()[0],
+                token: TokenText::new(x),
+                span: self.span(x),
             }),
         }
     }
 }
 
-impl TryFrom<&str> for Ws6in1Data {
-    type Error = super::Error;
+/// Value of a single parsed token.
+enum Value {
+    Ignored,
+    Date(Date),
+    Time(Time),
+    Number(f32),
+}
 
-    fn try_from(msg: &str) -> Result<Self> {
-        let mut iter = TokenIterator::new(msg.split_whitespace());
-        let _history_pct = iter.next()?;
+impl Value {
+    fn parse(token: &Ws6in1Token, text: &str) -> Option<Self> {
+        let value = match token.r#type {
+            TokenType::Ignore => Self::Ignored,
+            TokenType::Date => {
+                Self::Date(Date::parse(text, Ws6in1Data::DATE_FORMAT).ok()?)
+            }
+            TokenType::Time => {
+                Self::Time(Time::parse(text, Ws6in1Data::TIME_FORMAT).ok()?)
+            }
+            // Rust also parses "NaN" and "inf" which are never valid.
+            TokenType::Decimal => Self::Number(
+                text.parse::<f32>().ok().filter(|x| x.is_finite())?,
+            ),
+            TokenType::Integer => {
+                Self::Number(f32::from(text.parse::<u16>().ok()?))
+            }
+        };
+
+        // Reject values which do not fit into the integer data fields.
+        match (token.field, &value) {
+            (
+                Field::IndoorHumidity
+                | Field::OutdoorHumidity
+                | Field::ExtHumidity(_),
+                Self::Number(x),
+            ) if f32::from(*x as u8) != *x => None,
+            (Field::WindDir, Self::Number(x)) if f32::from(*x as u16) != *x => {
+                None
+            }
+            _ => Some(value),
+        }
+    }
+}
+
+/// Values collected from a message before they are assembled into
+/// [Ws6in1Data].
+#[derive(Default)]
+struct RawValues<'a> {
+    date: Option<Date>,
+    time: Option<Time>,
+    temperature_in: Option<f32>,
+    humidity_in: Option<f32>,
+    temperature_out: Option<f32>,
+    humidity_out: Option<f32>,
+    rain_day: Option<f32>,
+    rain_day_token: &'a str,
+    rain_actual: Option<f32>,
+    wind_actual: Option<f32>,
+    wind_gust: Option<f32>,
+    wind_dir: Option<f32>,
+    baro_sea: Option<f32>,
+    baro_sea_token: &'a str,
+    baro_absolute: Option<f32>,
+    uv_index: Option<f32>,
+    dew_point: Option<f32>,
+    ext_temperature: [Option<f32>; Ws6in1Data::EXT_SENSOR_COUNT],
+    ext_humidity: [Option<f32>; Ws6in1Data::EXT_SENSOR_COUNT],
+}
+
+impl<'a> RawValues<'a> {
+    fn set(&mut self, field: Field, text: &'a str, value: Value) -> Result<()> {
+        let number = match value {
+            Value::Ignored => return Ok(()),
+            Value::Date(x) if field == Field::Date => {
+                self.date = Some(x);
+                return Ok(());
+            }
+            Value::Time(x) if field == Field::Time => {
+                self.time = Some(x);
+                return Ok(());
+            }
+            Value::Number(x) => x,
+            _ => return Err(Error::InvalidLayout { field }),
+        };
+
+        let slot = match field {
+            Field::IndoorTemperature => &mut self.temperature_in,
+            Field::IndoorHumidity => &mut self.humidity_in,
+            Field::OutdoorTemperature => &mut self.temperature_out,
+            Field::OutdoorHumidity => &mut self.humidity_out,
+            Field::RainDay => {
+                self.rain_day_token = text;
+                &mut self.rain_day
+            }
+            Field::RainActual => &mut self.rain_actual,
+            Field::WindActual => &mut self.wind_actual,
+            Field::WindGust => &mut self.wind_gust,
+            Field::WindDir => &mut self.wind_dir,
+            Field::BaroSea => {
+                self.baro_sea_token = text;
+                &mut self.baro_sea
+            }
+            Field::BaroAbsolute => &mut self.baro_absolute,
+            Field::UvIndex => &mut self.uv_index,
+            Field::DewPoint => &mut self.dew_point,
+            Field::ExtTemperature(i) => self
+                .ext_temperature
+                .get_mut(i as usize)
+                .ok_or(Error::InvalidLayout { field })?,
+            Field::ExtHumidity(i) => self
+                .ext_humidity
+                .get_mut(i as usize)
+                .ok_or(Error::InvalidLayout { field })?,
+            Field::HistoryPct
+            | Field::Date
+            | Field::Time
+            | Field::WindOctant
+            | Field::Unknown => return Err(Error::InvalidLayout { field }),
+        };
+        *slot = Some(number);
+
+        Ok(())
+    }
+}
+
+impl RawValues<'_> {
+    fn check_humidity(&self, warnings: &mut Ws6in1ParseWarnings) {
+        let humidities = [
+            (Field::IndoorHumidity, self.humidity_in),
+            (Field::OutdoorHumidity, self.humidity_out),
+        ]
+        .into_iter()
+        .chain(
+            self.ext_humidity
+                .iter()
+                .enumerate()
+                .map(|(i, x)| (Field::ExtHumidity(i as u8), *x)),
+        );
+
+        for (field, humidity) in humidities {
+            if humidity.is_some_and(|x| x > 100.0) {
+                warnings.push(Ws6in1ParseWarning::OutOfRange { field });
+            }
+        }
+    }
+}
+
+fn required<T>(value: Option<T>, field: Field) -> Result<T> {
+    value.ok_or(Error::MissingField { field })
+}
+
+impl Ws6in1Data {
+    /// Parses a message using the given parser configuration.
+    /// Warnings from lenient parsing are discarded.
+    pub fn parse(msg: &str, config: &Ws6in1ParseConfig<'_>) -> Result<Self> {
+        Self::parse_with_warnings(msg, config).map(|(data, _)| data)
+    }
+
+    /// Parses a message using the given parser configuration and returns
+    /// all warnings found during lenient parsing. In
+    /// [ParseMode::Strict] the returned warnings are always empty.
+    pub fn parse_with_warnings(
+        msg: &str,
+        config: &Ws6in1ParseConfig<'_>,
+    ) -> Result<(Self, Ws6in1ParseWarnings)> {
+        let lenient = config.mode == ParseMode::Lenient;
+        let mut warnings = Ws6in1ParseWarnings::default();
+
+        let layout = match config.layout.select(msg)? {
+            Some(layout) => layout,
+            None => {
+                let tokens = msg.split_whitespace().count();
+                match config.layout.fallback() {
+                    Some(layout) if lenient => {
+                        warnings
+                            .push(Ws6in1ParseWarning::UnknownLayout { tokens });
+                        layout
+                    }
+                    _ => return Err(Error::UnknownLayout { tokens }),
+                }
+            }
+        };
+
+        let mut iter = TokenIterator::new(msg);
+        let mut raw = RawValues::default();
+        for token in layout.tokens {
+            let (text, span) = match iter.next(token.field) {
+                Ok(x) => x,
+                Err(_) if lenient => {
+                    warnings.push(Ws6in1ParseWarning::UnexpectedEnd {
+                        field: token.field,
+                    });
+                    break;
+                }
+                Err(e) => return Err(e),
+            };
+            match Value::parse(token, text) {
+                Some(value) => raw.set(token.field, text, value)?,
+                None if token.placeholder.matches(text, lenient) => (),
+                None if lenient && token.placeholder != Placeholder::None => {
+                    warnings.push(Ws6in1ParseWarning::InvalidField {
+                        field: token.field,
+                        span,
+                    });
+                }
+                None => {
+                    return Err(Error::InvalidToken {
+                        field: token.field,
+                        token: TokenText::new(text),
+                        span,
+                    })
+                }
+            }
+        }
+        if lenient {
+            let count = iter.remaining();
+            if count != 0 {
+                warnings.push(Ws6in1ParseWarning::TrailingTokens { count });
+            }
+        } else {
+            iter.end()?;
+        }
+
+        if lenient {
+            raw.check_humidity(&mut warnings);
+        }
 
-        let date = Date::parse(iter.next()?, Self::DATE_FORMAT)
-            .map_err(|_| Error::InvalidToken { tpos: iter.pos() })?;
-        let time = Time::parse(iter.next()?, Self::TIME_FORMAT)
-            .map_err(|_| Error::InvalidToken { tpos: iter.pos() })?;
+        let date = required(raw.date, Field::Date)?;
+        let time = required(raw.time, Field::Time)?;
         let local_timestamp =
             PrimitiveDateTime::new(date, time).as_utc().unix_timestamp();
 
-        let temperature_in = iter
-            .next()?
-            .parse::<f32>()
-            .map_err(|_| Error::InvalidToken { tpos: iter.pos() })?;
-        let humidity_in = iter
-            .next()?
-            .parse::<u8>()
-            .map_err(|_| Error::InvalidToken { tpos: iter.pos() })?;
-
-        let temperature_out = iter.next()?.parse::<f32>().ok();
-        let humidity_out = iter.next()?.parse::<u8>().ok();
-
-        let rain_day = iter.next()?.parse::<f32>().ok();
-        let rain_actual = iter.next()?.parse::<f32>().ok();
-
-        let wind_actual = iter.next()?.parse::<f32>().ok();
-        let wind_gust = iter.next()?.parse::<f32>().ok();
-        let wind_dir = iter.next()?.parse::<u16>().ok();
-        let _wind_octant = iter.next()?;
-
-        let baro_sea = iter
-            .next()?
-            .parse::<u16>()
-            .map_err(|_| Error::InvalidToken { tpos: iter.pos() })?;
-        let baro_absolute = iter
-            .next()?
-            .parse::<u16>()
-            .map_err(|_| Error::InvalidToken { tpos: iter.pos() })?;
-
-        let uv_index = iter.next()?.parse::<f32>().ok();
-        let dew_point = iter.next()?.parse::<f32>().ok();
-        let _unknown = iter.next()?;
+        let temperature_in =
+            required(raw.temperature_in, Field::IndoorTemperature)?;
+        let humidity_in = required(raw.humidity_in, Field::IndoorHumidity)?;
+        let baro_sea = required(raw.baro_sea, Field::BaroSea)?;
+        let baro_absolute = required(raw.baro_absolute, Field::BaroAbsolute)?;
+
+        let units = Ws6in1Units {
+            temperature: config
+                .temperature
+                .resolve(|| config::detect_temperature(temperature_in)),
+            pressure: config.pressure.resolve(|| {
+                config::detect_pressure(raw.baro_sea_token, baro_sea)
+            }),
+            speed: config.speed,
+            precipitation: config
+                .precipitation
+                .resolve(|| config::detect_precipitation(raw.rain_day_token)),
+        };
 
         let indoor = Ws6in1IndoorData {
-            temperature: temperature_in,
-            humidity: humidity_in,
-            baro_absolute,
-            baro_sea,
+            temperature: units.temperature.to_canonical(temperature_in),
+            humidity: Percent(humidity_in as u8),
+            baro_absolute: units.pressure.to_canonical(baro_absolute),
+            baro_sea: units.pressure.to_canonical(baro_sea),
         };
 
         let outdoor = if let (
@@ -185,59 +447,73 @@ impl TryFrom<&str> for Ws6in1Data {
             Some(uv_index),
             Some(dew_point),
         ) = (
-            temperature_out,
-            humidity_out,
-            rain_day,
-            rain_actual,
-            wind_actual,
-            wind_gust,
-            wind_dir,
-            uv_index,
-            dew_point,
+            raw.temperature_out,
+            raw.humidity_out,
+            raw.rain_day,
+            raw.rain_actual,
+            raw.wind_actual,
+            raw.wind_gust,
+            raw.wind_dir,
+            raw.uv_index,
+            raw.dew_point,
         ) {
             Some(Ws6in1OutdoorData {
-                temperature,
-                humidity,
-                rain_day,
-                rain_actual,
-                wind_actual,
-                wind_gust,
-                wind_dir,
-                uv_index,
-                dew_point,
+                temperature: units.temperature.to_canonical(temperature),
+                humidity: Percent(humidity as u8),
+                rain_day: units.precipitation.to_canonical(rain_day),
+                rain_actual: units.precipitation.to_canonical(rain_actual),
+                wind_actual: units.speed.to_canonical(wind_actual),
+                wind_gust: units.speed.to_canonical(wind_gust),
+                wind_dir: Degrees(wind_dir as u16),
+                uv_index: UvIndex(uv_index),
+                dew_point: units.temperature.to_canonical(dew_point),
             })
         } else {
             None
         };
 
         let mut ext = [None; Self::EXT_SENSOR_COUNT];
-        for i in ext.iter_mut() {
-            let temperature = iter.next()?.parse::<f32>().ok();
-            let humidity = iter.next()?.parse::<u8>().ok();
-
-            if let (Some(temperature), Some(humidity)) = (temperature, humidity)
+        for (i, ext) in ext.iter_mut().enumerate() {
+            if let (Some(temperature), Some(humidity)) =
+                (raw.ext_temperature[i], raw.ext_humidity[i])
             {
-                *i = Some(Ws6in1ExtData {
-                    temperature,
-                    humidity,
+                *ext = Some(Ws6in1ExtData {
+                    temperature: units.temperature.to_canonical(temperature),
+                    humidity: Percent(humidity as u8),
                 })
             }
         }
 
-        iter.end()?;
-
-        Ok(Self {
+        let mut data = Self {
             local_timestamp,
             indoor,
             outdoor,
             ext,
-        })
+            console_units: units,
+            received: None,
+            quality: Ws6in1Quality::default(),
+        };
+        data.quality = data.validate();
+
+        Ok((data, warnings))
+    }
+}
+
+impl TryFrom<&str> for Ws6in1Data {
+    type Error = super::Error;
+
+    /// Parses a message from a console with metric display settings.
+    fn try_from(msg: &str) -> Result<Self> {
+        Self::parse(msg, &Ws6in1ParseConfig::default())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::units::{
+        PrecipitationUnit, PressureUnit, SpeedUnit, TemperatureUnit,
+    };
 
     #[test]
     fn test_parse_data1() {
@@ -248,30 +524,30 @@ mod tests {
         let expected = Ws6in1Data {
             local_timestamp: 1579282200,
             indoor: Ws6in1IndoorData {
-                temperature: 20.4,
-                humidity: 49,
-                baro_sea: 1017,
-                baro_absolute: 954,
+                temperature: Celsius(20.4),
+                humidity: Percent(49),
+                baro_sea: Hectopascal(1017.0),
+                baro_absolute: Hectopascal(954.0),
             },
             outdoor: Some(Ws6in1OutdoorData {
-                temperature: 6.0,
-                humidity: 60,
-                rain_day: 0.0,
-                rain_actual: 0.0,
-                wind_actual: 0.0,
-                wind_gust: 0.0,
-                wind_dir: 129,
-                uv_index: 0.0,
-                dew_point: -1.2,
+                temperature: Celsius(6.0),
+                humidity: Percent(60),
+                rain_day: Millimeters(0.0),
+                rain_actual: Millimeters(0.0),
+                wind_actual: MetersPerSecond(0.0),
+                wind_gust: MetersPerSecond(0.0),
+                wind_dir: Degrees(129),
+                uv_index: UvIndex(0.0),
+                dew_point: Celsius(-1.2),
             }),
             ext: [
                 Some(Ws6in1ExtData {
-                    temperature: 27.3,
-                    humidity: 57,
+                    temperature: Celsius(27.3),
+                    humidity: Percent(57),
                 }),
                 Some(Ws6in1ExtData {
-                    temperature: 33.4,
-                    humidity: 40,
+                    temperature: Celsius(33.4),
+                    humidity: Percent(40),
                 }),
                 None,
                 None,
@@ -279,6 +555,9 @@ mod tests {
                 None,
                 None,
             ],
+            console_units: Ws6in1Units::default(),
+            received: None,
+            quality: Ws6in1Quality::default(),
         };
 
         match TryInto::<Ws6in1Data>::try_into(str) {
@@ -296,36 +575,39 @@ mod tests {
         let expected = Ws6in1Data {
             local_timestamp: 1737332340,
             indoor: Ws6in1IndoorData {
-                temperature: 19.5,
-                humidity: 38,
-                baro_sea: 1014,
-                baro_absolute: 954,
+                temperature: Celsius(19.5),
+                humidity: Percent(38),
+                baro_sea: Hectopascal(1014.0),
+                baro_absolute: Hectopascal(954.0),
             },
             outdoor: None,
             ext: [
                 Some(Ws6in1ExtData {
-                    temperature: 18.6,
-                    humidity: 52,
+                    temperature: Celsius(18.6),
+                    humidity: Percent(52),
                 }),
                 Some(Ws6in1ExtData {
-                    temperature: 2.3,
-                    humidity: 82,
+                    temperature: Celsius(2.3),
+                    humidity: Percent(82),
                 }),
                 Some(Ws6in1ExtData {
-                    temperature: 20.9,
-                    humidity: 35,
+                    temperature: Celsius(20.9),
+                    humidity: Percent(35),
                 }),
                 Some(Ws6in1ExtData {
-                    temperature: 19.1,
-                    humidity: 38,
+                    temperature: Celsius(19.1),
+                    humidity: Percent(38),
                 }),
                 Some(Ws6in1ExtData {
-                    temperature: 22.3,
-                    humidity: 41,
+                    temperature: Celsius(22.3),
+                    humidity: Percent(41),
                 }),
                 None,
                 None,
             ],
+            console_units: Ws6in1Units::default(),
+            received: None,
+            quality: Ws6in1Quality::default(),
         };
 
         match TryInto::<Ws6in1Data>::try_into(str) {
@@ -333,4 +615,316 @@ mod tests {
             Err(e) => panic!("Parsing Ws6in1Data failed: {e}"),
         }
     }
+
+    #[test]
+    fn test_parse_imperial_data() {
+        let str = "3 2020-01-17 17:30 68.0 49 32.0 60 1.00 0.00 22.4 44.7 \
+            129 SE 29.92 28.17 0 30.2 --.- 81.5 57 --.- -- --.- -- --.- \
+            -- --.- -- --.- -- --.- --";
+
+        let config = Ws6in1ParseConfig::detect(SpeedUnit::MilesPerHour);
+        let parsed = match Ws6in1Data::parse(str, &config) {
+            Ok(parsed) => parsed,
+            Err(e) => panic!("Parsing Ws6in1Data failed: {e}"),
+        };
+
+        let expected_units = Ws6in1Units {
+            temperature: TemperatureUnit::Fahrenheit,
+            pressure: PressureUnit::InchesOfMercury,
+            speed: SpeedUnit::MilesPerHour,
+            precipitation: PrecipitationUnit::Inches,
+        };
+        assert_eq!(expected_units, parsed.console_units);
+
+        let outdoor = parsed.outdoor.unwrap();
+        assert!((parsed.indoor.temperature.0 - 20.0).abs() < 1e-3);
+        assert!((parsed.indoor.baro_sea.0 - 1013.2).abs() < 0.1);
+        assert!(outdoor.temperature.0.abs() < 1e-3);
+        assert!((outdoor.rain_day.0 - 25.4).abs() < 1e-3);
+        assert!((outdoor.wind_gust.0 - 19.98).abs() < 0.01);
+        assert!((outdoor.dew_point.0 + 1.0).abs() < 1e-3);
+        assert!((parsed.ext[0].unwrap().temperature.0 - 27.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_detect_metric_data() {
+        let str = "3 2020-01-17 17:30 20.4 49 6.0 60 0.0 0.0 0.0 0.0 129 \
+            SE 1017 954 0 -1.2 --.- 27.3 57 33.4 40 --.- -- --.- -- --.- \
+            -- --.- -- --.- --";
+
+        let config = Ws6in1ParseConfig::detect(SpeedUnit::MetersPerSecond);
+        match Ws6in1Data::parse(str, &config) {
+            Ok(parsed) => {
+                assert_eq!(Ws6in1Units::default(), parsed.console_units);
+                assert_eq!(Hectopascal(1017.0), parsed.indoor.baro_sea);
+            }
+            Err(e) => panic!("Parsing Ws6in1Data failed: {e}"),
+        }
+    }
+
+    static SHORT_LAYOUT: Ws6in1Layout<'static> = Ws6in1Layout {
+        name: "short",
+        tokens: &[
+            Ws6in1Token::required(Field::Date, TokenType::Date),
+            Ws6in1Token::required(Field::Time, TokenType::Time),
+            Ws6in1Token::required(Field::IndoorTemperature, TokenType::Decimal),
+            Ws6in1Token::required(Field::IndoorHumidity, TokenType::Integer),
+            Ws6in1Token::required(Field::BaroSea, TokenType::Integer),
+            Ws6in1Token::required(Field::BaroAbsolute, TokenType::Integer),
+            Ws6in1Token {
+                field: Field::ExtTemperature(0),
+                r#type: TokenType::Decimal,
+                placeholder: Placeholder::Dashes,
+            },
+            Ws6in1Token::optional(Field::ExtHumidity(0), TokenType::Integer),
+        ],
+    };
+    static LAYOUTS: [&Ws6in1Layout<'static>; 2] =
+        [&Ws6in1Layout::DEFAULT, &SHORT_LAYOUT];
+
+    #[test]
+    fn test_parse_custom_layout() {
+        let config = Ws6in1ParseConfig {
+            layout: LayoutSelection::Detect(&LAYOUTS),
+            ..Default::default()
+        };
+
+        let parsed = Ws6in1Data::parse(
+            "2020-01-17 17:30 20.4 49 1017 954 27.3 57",
+            &config,
+        )
+        .unwrap();
+        assert_eq!(1579282200, parsed.local_timestamp);
+        assert_eq!(Hectopascal(954.0), parsed.indoor.baro_absolute);
+        assert!(parsed.outdoor.is_none());
+        assert_eq!(
+            Some(Ws6in1ExtData {
+                temperature: Celsius(27.3),
+                humidity: Percent(57),
+            }),
+            parsed.ext[0]
+        );
+
+        let parsed = Ws6in1Data::parse(
+            "2020-01-17 17:30 20.4 49 1017 954 --.- --",
+            &config,
+        )
+        .unwrap();
+        assert!(parsed.ext[0].is_none());
+
+        assert!(matches!(
+            Ws6in1Data::parse(
+                "2020-01-17 17:30 20.4 49 1017 954 x 57",
+                &config
+            ),
+            Err(Error::InvalidToken { .. })
+        ));
+        assert!(Ws6in1Data::parse("2020-01-17 17:30 20.4 49 1017", &config)
+            .is_err_and(|e| matches!(e, Error::UnknownLayout { tokens: 5 })
+                && !e.is_transient()));
+    }
+
+    #[test]
+    fn test_detect_layout_by_token_type() {
+        let tokens = vec![
+            Ws6in1Token::required(Field::Date, TokenType::Date),
+            Ws6in1Token::required(Field::Time, TokenType::Time),
+            Ws6in1Token::required(Field::IndoorTemperature, TokenType::Decimal),
+            Ws6in1Token::required(Field::IndoorHumidity, TokenType::Integer),
+            Ws6in1Token::required(Field::BaroSea, TokenType::Integer),
+            Ws6in1Token::required(Field::BaroAbsolute, TokenType::Integer),
+            Ws6in1Token::optional(Field::ExtHumidity(0), TokenType::Integer),
+            Ws6in1Token::optional(Field::ExtTemperature(0), TokenType::Decimal),
+        ];
+        let swapped = Ws6in1Layout {
+            name: "swapped",
+            tokens: &tokens,
+        };
+        let layouts = [&SHORT_LAYOUT, &swapped];
+        let config = Ws6in1ParseConfig {
+            layout: LayoutSelection::Detect(&layouts),
+            ..Default::default()
+        };
+
+        let parsed = Ws6in1Data::parse(
+            "2020-01-17 17:30 20.4 49 1017 954 27.3 57",
+            &config,
+        )
+        .unwrap();
+        assert_eq!(Celsius(27.3), parsed.ext[0].unwrap().temperature);
+
+        let parsed = Ws6in1Data::parse(
+            "2020-01-17 17:30 20.4 49 1017 954 57 27.3",
+            &config,
+        )
+        .unwrap();
+        assert_eq!(Celsius(27.3), parsed.ext[0].unwrap().temperature);
+
+        assert!(Ws6in1Data::parse(
+            "2020-01-17 17:30 20.4 49 1017 954 x 57",
+            &config
+        )
+        .is_err_and(|e| matches!(e, Error::AmbiguousLayout { tokens: 8 })
+            && !e.is_transient()));
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let str = "3 2020-01-17 17:30 20.4 49 6.0 160 0.0 0.0 0.0 0.0 129 \
+            SE 1017 954 0 -1.2 --.- 27.3 57 33.4 40 --.- -- --.- -- --.- \
+            -- --.- -- --.- -- 42 43";
+
+        let strict = Ws6in1ParseConfig::default();
+        assert!(matches!(
+            Ws6in1Data::parse(str, &strict),
+            Err(Error::GarbageEnd { .. })
+        ));
+
+        let lenient = Ws6in1ParseConfig {
+            mode: ParseMode::Lenient,
+            ..Default::default()
+        };
+        let (parsed, warnings) =
+            Ws6in1Data::parse_with_warnings(str, &lenient).unwrap();
+        let warnings: Vec<_> = warnings.iter().copied().collect();
+
+        assert_eq!(Percent(160), parsed.outdoor.unwrap().humidity);
+        assert_eq!(
+            vec![
+                Ws6in1ParseWarning::TrailingTokens { count: 2 },
+                Ws6in1ParseWarning::OutOfRange {
+                    field: Field::OutdoorHumidity
+                },
+            ],
+            warnings
+        );
+    }
+
+    #[test]
+    fn test_parse_non_dash_placeholder() {
+        let str = "3 2020-01-17 17:30 20.4 49 6.0 60 0.0 0.0 0.0 0.0 129 \
+            SE 1017 954 0 -1.2 --.- 27.3 57 n/a 40 --.- -- --.- -- --.- \
+            -- --.- -- --.- --";
+
+        let parsed = Ws6in1Data::try_from(str).unwrap();
+        assert!(parsed.ext[1].is_none());
+
+        let lenient = Ws6in1ParseConfig {
+            mode: ParseMode::Lenient,
+            ..Default::default()
+        };
+        let (parsed, warnings) =
+            Ws6in1Data::parse_with_warnings(str, &lenient).unwrap();
+        let warnings: Vec<_> = warnings.iter().copied().collect();
+
+        assert!(parsed.ext[1].is_none());
+        assert_eq!(
+            vec![Ws6in1ParseWarning::InvalidField {
+                field: Field::ExtTemperature(1),
+                span: Span { start: 86, end: 89 },
+            }],
+            warnings
+        );
+        assert_eq!("n/a", &str[86..89]);
+    }
+
+    #[test]
+    fn test_parse_lenient_truncated() {
+        let str = "3 2020-01-17 17:30 20.4 49 6.0 60 0.0 0.0 0.0 0.0 129 \
+            SE 1017 954 0 -1.2 --.- 27.3 5x 33.4";
+
+        let lenient = Ws6in1ParseConfig {
+            mode: ParseMode::Lenient,
+            layout: LayoutSelection::Detect(&LAYOUTS),
+            ..Default::default()
+        };
+        let (parsed, warnings) =
+            Ws6in1Data::parse_with_warnings(str, &lenient).unwrap();
+        let warnings: Vec<_> = warnings.iter().copied().collect();
+
+        assert!(parsed.outdoor.is_some());
+        assert_eq!([None; Ws6in1Data::EXT_SENSOR_COUNT], parsed.ext);
+        assert_eq!(
+            vec![
+                Ws6in1ParseWarning::UnknownLayout { tokens: 21 },
+                Ws6in1ParseWarning::InvalidField {
+                    field: Field::ExtHumidity(0),
+                    span: Span { start: 83, end: 85 },
+                },
+                Ws6in1ParseWarning::UnexpectedEnd {
+                    field: Field::ExtHumidity(1),
+                },
+            ],
+            warnings
+        );
+        assert_eq!("5x", &str[83..85]);
+    }
+
+    #[test]
+    fn test_invalid_token_diagnostic() {
+        let str = "3 2020-01-17 17:30 20.4 49 6.0 60 0.0 0.0 0.0 0.0 129 \
+            SE 10x7 954 0 -1.2 --.- 27.3 57 33.4 40 --.- -- --.- -- --.- \
+            -- --.- -- --.- --";
+
+        let e = Ws6in1Data::try_from(str).unwrap_err();
+        let span = Span { start: 57, end: 61 };
+        match e {
+            Error::InvalidToken {
+                field,
+                token,
+                span: s,
+            } => {
+                assert_eq!(Field::BaroSea, field);
+                assert_eq!("10x7", token.as_str());
+                assert_eq!(span, s);
+            }
+            e => panic!("Unexpected error: {e}"),
+        }
+
+        let diagnostic = format!("{}", e.diagnostic(str));
+        let mut lines = diagnostic.lines();
+        assert_eq!(
+            Some(
+                "Parsing field BaroSea from token \"10x7\" at bytes \
+                57..61 failed"
+            ),
+            lines.next()
+        );
+        assert_eq!(Some(str), lines.next());
+        assert_eq!(
+            Some(format!("{}^^^^", " ".repeat(57)).as_str()),
+            lines.next()
+        );
+    }
+
+    #[test]
+    fn test_reject_non_finite_baro() {
+        for baro in ["NaN", "inf", "-inf"] {
+            let str = format!(
+                "3 2020-01-17 17:30 20.4 49 6.0 60 0.0 0.0 0.0 0.0 129 \
+                SE {baro} 954 0 -1.2 --.- 27.3 57 33.4 40 --.- -- --.- -- \
+                --.- -- --.- -- --.- --"
+            );
+
+            match Ws6in1Data::try_from(str.as_str()) {
+                Err(Error::InvalidToken { field, .. }) => {
+                    assert_eq!(Field::BaroSea, field)
+                }
+                x => panic!("Unexpected result: {x:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_unexpected_end() {
+        let str = "3 2020-01-17 17:30 20.4 49 6.0 60 0.0 0.0 0.0 0.0 129 ";
+
+        match Ws6in1Data::try_from(str) {
+            Err(Error::UnexpectedEnd { field, offset }) => {
+                assert_eq!(Field::WindOctant, field);
+                assert_eq!(str.len() - 1, offset);
+            }
+            x => panic!("Unexpected result: {x:?}"),
+        }
+    }
 }
@@ -0,0 +1,259 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use time::{Duration, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+#[cfg(feature = "tz")]
+use time_tz::{Offset, OffsetResult, TimeZone};
+
+use super::Ws6in1Data;
+use crate::{Error, Result};
+
+/// Strategy for console local times which are ambiguous or do not exist
+/// because of a daylight saving time transition.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum DstPolicy {
+    /// Use the earlier of both possible instants.
+    #[default]
+    Earlier,
+    /// Use the later of both possible instants.
+    Later,
+    /// Return an error.
+    Reject,
+}
+
+impl Ws6in1Data {
+    /// Returns the consoles local wall clock time of this reading.
+    pub fn local_datetime(&self) -> Result<PrimitiveDateTime> {
+        let datetime = OffsetDateTime::from_unix_timestamp(
+            self.local_timestamp,
+        )
+        .map_err(|_| Error::InvalidTimestamp {
+            timestamp: self.local_timestamp,
+        })?;
+
+        Ok(PrimitiveDateTime::new(datetime.date(), datetime.time()))
+    }
+
+    /// Converts the consoles local time into UTC assuming the console
+    /// runs with the given fixed UTC offset.
+    pub fn utc_datetime(&self, offset: UtcOffset) -> Result<OffsetDateTime> {
+        Ok(self
+            .local_datetime()?
+            .assume_offset(offset)
+            .to_offset(UtcOffset::UTC))
+    }
+
+    /// Returns the host receive time clamped to the consoles minute.
+    ///
+    /// The host receive time is shifted by `clock_offset`, the known
+    /// difference between console and host clock, before it is clamped to
+    /// the minute reported by the console running with `console_offset`.
+    /// This yields sub-minute resolution for readings received within the
+    /// same console minute. Returns [None] if no receive time is attached.
+    pub fn reconciled_datetime(
+        &self,
+        console_offset: UtcOffset,
+        clock_offset: Duration,
+    ) -> Result<Option<OffsetDateTime>> {
+        let Some(received) = self.received else {
+            return Ok(None);
+        };
+
+        let minute_start = self.utc_datetime(console_offset)?;
+        let minute_end = minute_start + Duration::MINUTE - Duration::NANOSECOND;
+        let corrected = (received + clock_offset).to_offset(UtcOffset::UTC);
+
+        Ok(Some(corrected.clamp(minute_start, minute_end)))
+    }
+
+    /// Converts the consoles local time into UTC assuming the console
+    /// follows the given timezone including daylight saving time.
+    ///
+    /// Local times during the repeated hour in autumn are ambiguous and
+    /// local times during the skipped hour in spring do not exist.
+    /// Both cases are resolved using the given [DstPolicy]. Nonexistent
+    /// times are interpreted with the offset before and after the
+    /// transition which yields the two possible instants.
+    #[cfg(feature = "tz")]
+    pub fn utc_datetime_tz<T: TimeZone>(
+        &self,
+        tz: &T,
+        policy: DstPolicy,
+    ) -> Result<OffsetDateTime> {
+        let local = self.local_datetime()?;
+        let probe = local.assume_utc();
+
+        let (earlier, later) = match tz.get_offset_local(&probe) {
+            OffsetResult::Some(offset) => {
+                let datetime = local.assume_offset(offset.to_utc());
+                (datetime, datetime)
+            }
+            OffsetResult::Ambiguous(a, b) => {
+                if policy == DstPolicy::Reject {
+                    return Err(Error::AmbiguousLocalTime {
+                        timestamp: self.local_timestamp,
+                    });
+                }
+                ordered(
+                    local.assume_offset(a.to_utc()),
+                    local.assume_offset(b.to_utc()),
+                )
+            }
+            OffsetResult::None => {
+                if policy == DstPolicy::Reject {
+                    return Err(Error::NonexistentLocalTime {
+                        timestamp: self.local_timestamp,
+                    });
+                }
+                let before = tz.get_offset_utc(&(probe - Duration::DAY));
+                let after = tz.get_offset_utc(&(probe + Duration::DAY));
+                ordered(
+                    local.assume_offset(before.to_utc()),
+                    local.assume_offset(after.to_utc()),
+                )
+            }
+        };
+
+        let datetime = match policy {
+            DstPolicy::Later => later,
+            _ => earlier,
+        };
+
+        Ok(datetime.to_offset(UtcOffset::UTC))
+    }
+}
+
+#[cfg(feature = "tz")]
+fn ordered(
+    a: OffsetDateTime,
+    b: OffsetDateTime,
+) -> (OffsetDateTime, OffsetDateTime) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{datetime, offset};
+
+    use super::*;
+
+    fn data_at(local: PrimitiveDateTime) -> Ws6in1Data {
+        Ws6in1Data {
+            local_timestamp: local.assume_utc().unix_timestamp(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fixed_offset() {
+        let data = data_at(datetime!(2020-01-17 17:30));
+
+        assert_eq!(datetime!(2020-01-17 17:30), data.local_datetime().unwrap());
+        assert_eq!(
+            datetime!(2020-01-17 16:30 UTC),
+            data.utc_datetime(offset!(+1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_reconciled_datetime() {
+        let mut data = data_at(datetime!(2020-01-17 17:30));
+        assert!(data
+            .reconciled_datetime(offset!(+1), Duration::ZERO)
+            .unwrap()
+            .is_none());
+
+        data.received = Some(datetime!(2020-01-17 16:30:25.5 UTC));
+        assert_eq!(
+            Some(datetime!(2020-01-17 16:30:25.5 UTC)),
+            data.reconciled_datetime(offset!(+1), Duration::ZERO)
+                .unwrap()
+        );
+        assert_eq!(
+            Some(datetime!(2020-01-17 16:30:15.5 UTC)),
+            data.reconciled_datetime(offset!(+1), Duration::seconds(-10))
+                .unwrap()
+        );
+        assert_eq!(
+            Some(datetime!(2020-01-17 16:30:59.999_999_999 UTC)),
+            data.reconciled_datetime(offset!(+1), Duration::MINUTE)
+                .unwrap()
+        );
+        assert_eq!(
+            Some(datetime!(2020-01-17 16:30 UTC)),
+            data.reconciled_datetime(offset!(+1), -Duration::MINUTE)
+                .unwrap()
+        );
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn test_timezone_regular() {
+        let berlin = time_tz::timezones::get_by_name("Europe/Berlin").unwrap();
+        let data = data_at(datetime!(2025-07-01 12:00));
+
+        assert_eq!(
+            datetime!(2025-07-01 10:00 UTC),
+            data.utc_datetime_tz(berlin, DstPolicy::Reject).unwrap()
+        );
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn test_timezone_repeated_hour() {
+        let berlin = time_tz::timezones::get_by_name("Europe/Berlin").unwrap();
+        let data = data_at(datetime!(2025-10-26 02:30));
+
+        assert_eq!(
+            datetime!(2025-10-26 00:30 UTC),
+            data.utc_datetime_tz(berlin, DstPolicy::Earlier).unwrap()
+        );
+        assert_eq!(
+            datetime!(2025-10-26 01:30 UTC),
+            data.utc_datetime_tz(berlin, DstPolicy::Later).unwrap()
+        );
+        assert!(matches!(
+            data.utc_datetime_tz(berlin, DstPolicy::Reject),
+            Err(Error::AmbiguousLocalTime { .. })
+        ));
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn test_timezone_skipped_hour() {
+        let berlin = time_tz::timezones::get_by_name("Europe/Berlin").unwrap();
+        let data = data_at(datetime!(2025-03-30 02:30));
+
+        assert_eq!(
+            datetime!(2025-03-30 00:30 UTC),
+            data.utc_datetime_tz(berlin, DstPolicy::Earlier).unwrap()
+        );
+        assert_eq!(
+            datetime!(2025-03-30 01:30 UTC),
+            data.utc_datetime_tz(berlin, DstPolicy::Later).unwrap()
+        );
+        assert!(matches!(
+            data.utc_datetime_tz(berlin, DstPolicy::Reject),
+            Err(Error::NonexistentLocalTime { .. })
+        ));
+    }
+}
@@ -0,0 +1,341 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use super::{Field, Ws6in1Data};
+
+impl Field {
+    /// Fields without extra sensor channel in bit order.
+    const BASE: [Self; 18] = [
+        Self::HistoryPct,
+        Self::Date,
+        Self::Time,
+        Self::IndoorTemperature,
+        Self::IndoorHumidity,
+        Self::OutdoorTemperature,
+        Self::OutdoorHumidity,
+        Self::RainDay,
+        Self::RainActual,
+        Self::WindActual,
+        Self::WindGust,
+        Self::WindDir,
+        Self::WindOctant,
+        Self::BaroSea,
+        Self::BaroAbsolute,
+        Self::UvIndex,
+        Self::DewPoint,
+        Self::Unknown,
+    ];
+    const EXT_BASE: u32 = Self::BASE.len() as u32;
+
+    fn bit(self) -> u32 {
+        match self {
+            Self::ExtTemperature(i) => Self::EXT_BASE + 2 * u32::from(i),
+            Self::ExtHumidity(i) => Self::EXT_BASE + 2 * u32::from(i) + 1,
+            field => {
+                Self::BASE.iter().position(|x| *x == field).unwrap_or(0) as u32
+            }
+        }
+    }
+
+    fn from_bit(bit: u32) -> Self {
+        match bit.checked_sub(Self::EXT_BASE) {
+            None => Self::BASE[bit as usize],
+            Some(x) if x % 2 == 0 => Self::ExtTemperature((x / 2) as u8),
+            Some(x) => Self::ExtHumidity((x / 2) as u8),
+        }
+    }
+}
+
+/// Per field quality flags of a reading.
+///
+/// The parser validates every reading with the default
+/// [Ws6in1PlausibilityLimits] and stores the result in
+/// [Ws6in1Data::quality].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Ws6in1Quality {
+    suspect: u64,
+}
+
+impl Ws6in1Quality {
+    /// Returns true if no field was flagged as implausible.
+    pub fn is_plausible(&self) -> bool {
+        self.suspect == 0
+    }
+
+    /// Returns true if the given field was flagged as implausible.
+    pub fn is_suspect(&self, field: Field) -> bool {
+        field.bit() < u64::BITS && self.suspect & (1 << field.bit()) != 0
+    }
+
+    /// Flags the given field as implausible.
+    pub fn mark(&mut self, field: Field) {
+        if field.bit() < u64::BITS {
+            self.suspect |= 1 << field.bit();
+        }
+    }
+
+    /// Iterates over all fields flagged as implausible.
+    pub fn suspect_fields(&self) -> impl Iterator<Item = Field> + '_ {
+        (0..u64::BITS)
+            .filter(|x| self.suspect & (1 << x) != 0)
+            .map(Field::from_bit)
+    }
+}
+
+/// Limits of physically plausible values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ws6in1PlausibilityLimits {
+    /// Minimum plausible temperature in °C.
+    pub temperature_min: f32,
+    /// Maximum plausible temperature in °C.
+    pub temperature_max: f32,
+    /// Minimum plausible sea level pressure in hPa.
+    pub pressure_min: f32,
+    /// Maximum plausible sea level pressure in hPa.
+    pub pressure_max: f32,
+    /// Minimum plausible absolute pressure in hPa. It is far below the
+    /// sea level minimum to allow stations at high altitude.
+    pub absolute_pressure_min: f32,
+    /// Maximum plausible absolute pressure in hPa.
+    pub absolute_pressure_max: f32,
+    /// Maximum plausible wind speed in m/s.
+    pub wind_max: f32,
+    /// Maximum plausible UV index.
+    pub uv_index_max: f32,
+    /// Tolerance in Kelvin by which the dew point may exceed the
+    /// temperature.
+    pub dew_point_tolerance: f32,
+}
+
+impl Default for Ws6in1PlausibilityLimits {
+    fn default() -> Self {
+        Self {
+            temperature_min: -60.0,
+            temperature_max: 70.0,
+            pressure_min: 800.0,
+            pressure_max: 1100.0,
+            absolute_pressure_min: 300.0,
+            absolute_pressure_max: 1100.0,
+            wind_max: 90.0,
+            uv_index_max: 20.0,
+            dew_point_tolerance: 0.5,
+        }
+    }
+}
+
+impl Ws6in1PlausibilityLimits {
+    fn temperature(&self, value: f32) -> bool {
+        (self.temperature_min..=self.temperature_max).contains(&value)
+    }
+
+    fn pressure(&self, value: f32) -> bool {
+        (self.pressure_min..=self.pressure_max).contains(&value)
+    }
+
+    fn absolute_pressure(&self, value: f32) -> bool {
+        (self.absolute_pressure_min..=self.absolute_pressure_max)
+            .contains(&value)
+    }
+
+    fn wind(&self, value: f32) -> bool {
+        (0.0..=self.wind_max).contains(&value)
+    }
+}
+
+impl Ws6in1Data {
+    /// Checks all values against the default plausibility limits.
+    pub fn validate(&self) -> Ws6in1Quality {
+        self.validate_with(&Ws6in1PlausibilityLimits::default())
+    }
+
+    /// Checks all values against the given plausibility limits.
+    pub fn validate_with(
+        &self,
+        limits: &Ws6in1PlausibilityLimits,
+    ) -> Ws6in1Quality {
+        let mut quality = Ws6in1Quality::default();
+        let mut check = |field, plausible: bool| {
+            if !plausible {
+                quality.mark(field);
+            }
+        };
+
+        let indoor = &self.indoor;
+        check(
+            Field::IndoorTemperature,
+            limits.temperature(indoor.temperature.0),
+        );
+        check(Field::IndoorHumidity, indoor.humidity.0 <= 100);
+        check(Field::BaroSea, limits.pressure(indoor.baro_sea.0));
+        check(
+            Field::BaroAbsolute,
+            limits.absolute_pressure(indoor.baro_absolute.0),
+        );
+
+        if let Some(outdoor) = &self.outdoor {
+            check(
+                Field::OutdoorTemperature,
+                limits.temperature(outdoor.temperature.0),
+            );
+            check(Field::OutdoorHumidity, outdoor.humidity.0 <= 100);
+            check(Field::RainDay, outdoor.rain_day.0 >= 0.0);
+            check(Field::RainActual, outdoor.rain_actual.0 >= 0.0);
+            check(Field::WindActual, limits.wind(outdoor.wind_actual.0));
+            check(
+                Field::WindGust,
+                limits.wind(outdoor.wind_gust.0)
+                    && outdoor.wind_gust >= outdoor.wind_actual,
+            );
+            check(Field::WindDir, outdoor.wind_dir.0 <= 359);
+            check(
+                Field::UvIndex,
+                (0.0..=limits.uv_index_max).contains(&outdoor.uv_index.0),
+            );
+            check(
+                Field::DewPoint,
+                limits.temperature(outdoor.dew_point.0)
+                    && outdoor.dew_point.0
+                        <= outdoor.temperature.0 + limits.dew_point_tolerance,
+            );
+        }
+
+        for (idx, ext) in self.ext_iter() {
+            check(
+                Field::ExtTemperature(idx as u8),
+                limits.temperature(ext.temperature.0),
+            );
+            check(Field::ExtHumidity(idx as u8), ext.humidity.0 <= 100);
+        }
+
+        quality
+    }
+
+    /// Removes sensor data containing fields flagged in
+    /// [Ws6in1Data::quality]. Implausible outdoor or extra sensor fields
+    /// remove the data of the whole sensor. Indoor data is always kept.
+    pub fn suppress_implausible(&mut self) {
+        let quality = self.quality;
+        let outdoor_suspect = quality.suspect_fields().any(|x| {
+            matches!(
+                x,
+                Field::OutdoorTemperature
+                    | Field::OutdoorHumidity
+                    | Field::RainDay
+                    | Field::RainActual
+                    | Field::WindActual
+                    | Field::WindGust
+                    | Field::WindDir
+                    | Field::UvIndex
+                    | Field::DewPoint
+            )
+        });
+        if outdoor_suspect {
+            self.outdoor = None;
+        }
+
+        for (idx, ext) in self.ext.iter_mut().enumerate() {
+            if quality.is_suspect(Field::ExtTemperature(idx as u8))
+                || quality.is_suspect(Field::ExtHumidity(idx as u8))
+            {
+                *ext = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parser::{Ws6in1ExtData, Ws6in1IndoorData, Ws6in1OutdoorData},
+        units::{Celsius, Degrees, Hectopascal, MetersPerSecond, Percent},
+    };
+
+    fn plausible_data() -> Ws6in1Data {
+        let mut data = Ws6in1Data {
+            indoor: Ws6in1IndoorData {
+                temperature: Celsius(20.4),
+                humidity: Percent(49),
+                baro_sea: Hectopascal(1017.0),
+                baro_absolute: Hectopascal(954.0),
+            },
+            outdoor: Some(Ws6in1OutdoorData {
+                temperature: Celsius(6.0),
+                humidity: Percent(60),
+                wind_actual: MetersPerSecond(2.0),
+                wind_gust: MetersPerSecond(4.0),
+                wind_dir: Degrees(129),
+                dew_point: Celsius(-1.2),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        data.ext[1] = Some(Ws6in1ExtData {
+            temperature: Celsius(27.3),
+            humidity: Percent(57),
+        });
+        data
+    }
+
+    #[test]
+    fn test_plausible_data() {
+        assert!(plausible_data().validate().is_plausible());
+    }
+
+    #[test]
+    fn test_high_altitude_pressure() {
+        let mut data = plausible_data();
+        data.indoor.baro_absolute = Hectopascal(700.0);
+        assert!(data.validate().is_plausible());
+
+        data.indoor.baro_absolute = Hectopascal(250.0);
+        assert_eq!(
+            vec![Field::BaroAbsolute],
+            data.validate().suspect_fields().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_implausible_data() {
+        let mut data = plausible_data();
+        data.indoor.baro_sea = Hectopascal(1200.0);
+        data.ext[1].as_mut().unwrap().humidity = Percent(140);
+        let outdoor = data.outdoor.as_mut().unwrap();
+        outdoor.wind_gust = MetersPerSecond(1.0);
+        outdoor.wind_dir = Degrees(360);
+        outdoor.dew_point = Celsius(8.0);
+
+        data.quality = data.validate();
+        let fields: Vec<_> = data.quality.suspect_fields().collect();
+        assert_eq!(
+            vec![
+                Field::WindGust,
+                Field::WindDir,
+                Field::BaroSea,
+                Field::DewPoint,
+                Field::ExtHumidity(1),
+            ],
+            fields
+        );
+
+        data.suppress_implausible();
+        assert!(data.outdoor.is_none());
+        assert!(data.ext[1].is_none());
+        assert_eq!(Hectopascal(1200.0), data.indoor.baro_sea);
+    }
+}
@@ -0,0 +1,117 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use super::Field;
+use crate::Span;
+
+/// Selects how the parser handles malformed messages.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ParseMode {
+    /// Any malformed token fails parsing.
+    #[default]
+    Strict,
+    /// Malformed optional tokens, missing or trailing tokens are reported
+    /// as [Ws6in1ParseWarning] and parsing continues. Required tokens
+    /// must still be valid.
+    Lenient,
+}
+
+/// An irregularity found in a message during lenient parsing.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Ws6in1ParseWarning {
+    /// No layout matched the token count of the message, the first
+    /// configured layout was used.
+    UnknownLayout { tokens: usize },
+    /// The message ended before the given field. It and all remaining
+    /// fields are treated as missing.
+    UnexpectedEnd { field: Field },
+    /// The given amount of trailing tokens was ignored.
+    TrailingTokens { count: usize },
+    /// Parsing the given field from the token at `span` failed.
+    /// It is treated as missing.
+    InvalidField { field: Field, span: Span },
+    /// The value of the given field is outside its valid range.
+    OutOfRange { field: Field },
+}
+
+/// A fixed capacity list of [Ws6in1ParseWarning].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Ws6in1ParseWarnings {
+    items: [Option<Ws6in1ParseWarning>; Self::CAPACITY],
+    len: usize,
+    dropped: usize,
+}
+
+impl Ws6in1ParseWarnings {
+    /// Maximum amount of stored warnings.
+    pub const CAPACITY: usize = 16;
+
+    /// Appends a warning. Warnings exceeding the capacity are counted
+    /// but not stored.
+    pub fn push(&mut self, warning: Ws6in1ParseWarning) {
+        match self.items.get_mut(self.len) {
+            Some(slot) => {
+                *slot = Some(warning);
+                self.len += 1;
+            }
+            None => self.dropped += 1,
+        }
+    }
+
+    /// Returns the amount of stored warnings.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no warnings were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0 && self.dropped == 0
+    }
+
+    /// Returns the amount of warnings that did not fit into the list.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Iterates over all stored warnings.
+    pub fn iter(&self) -> impl Iterator<Item = &Ws6in1ParseWarning> {
+        self.items[..self.len].iter().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warnings_overflow() {
+        let mut warnings = Ws6in1ParseWarnings::default();
+        assert!(warnings.is_empty());
+
+        for count in 0..Ws6in1ParseWarnings::CAPACITY + 2 {
+            warnings.push(Ws6in1ParseWarning::TrailingTokens { count });
+        }
+
+        assert_eq!(Ws6in1ParseWarnings::CAPACITY, warnings.len());
+        assert_eq!(2, warnings.dropped());
+        assert_eq!(
+            Some(&Ws6in1ParseWarning::TrailingTokens { count: 0 }),
+            warnings.iter().next()
+        );
+    }
+}
@@ -44,12 +44,14 @@ impl<V: Ws6in1Container<u8>> Ws6in1Serde for AnyWs6in1MessageBase<V> {
     }
 
     fn deserialize(buffer: &mut Cursor<&[u8]>) -> Result<Self> {
-        buffer.check_remaining(1)?;
+        buffer.check_remaining(1).map_err(Error::truncated)?;
 
         let r#type = buffer.peek_u8(0);
         let message = match r#type {
             cmd::CMD_TYPE => {
-                buffer.check_remaining(cmd::CMD_LENGTH)?;
+                buffer
+                    .check_remaining(cmd::CMD_LENGTH)
+                    .map_err(Error::truncated)?;
                 let opcode = buffer.peek_u8(1);
                 match opcode {
                     Ws6in1SetDate::OPCODE => {
@@ -198,4 +200,39 @@ mod tests {
             panic!("Serialized message into too small buffer {x:?}");
         }
     }
+
+    #[test]
+    fn too_small_buffer_is_fatal() {
+        let cmd = AnyWs6in1MessageHeapless::SetDate(Ws6in1SetDate {
+            year: 20,
+            month: 1,
+            day: 17,
+        });
+
+        let mut buffer = [0u8; 4];
+        let mut cursor = Cursor::new(&mut buffer[..]);
+
+        match cmd.serialize(&mut cursor) {
+            Err(e @ Error::BufferTooSmall(_)) => assert!(!e.is_transient()),
+            x => panic!("Unexpected result {x:?}"),
+        }
+    }
+
+    #[test]
+    fn reject_truncated_message() {
+        let serialized = [0xFC, 0x09, 0x11, 0x0A];
+
+        let mut cursor = Cursor::new(&serialized[..]);
+        match AnyWs6in1MessageHeapless::deserialize(&mut cursor) {
+            Err(
+                e @ Error::MessageTruncated {
+                    len: 4,
+                    expected: 8,
+                },
+            ) => {
+                assert!(e.is_transient())
+            }
+            x => panic!("Unexpected result {x:?}"),
+        }
+    }
 }
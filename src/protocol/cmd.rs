@@ -53,7 +53,9 @@ impl Ws6in1Serde for Ws6in1SetDate {
     where
         Self: Sized,
     {
-        buffer.check_remaining(CMD_LENGTH)?;
+        buffer
+            .check_remaining(CMD_LENGTH)
+            .map_err(Error::truncated)?;
 
         let r#type = buffer.read_u8();
         if r#type != CMD_TYPE {
@@ -103,7 +105,9 @@ impl Ws6in1Serde for Ws6in1SetTime {
     where
         Self: Sized,
     {
-        buffer.check_remaining(CMD_LENGTH)?;
+        buffer
+            .check_remaining(CMD_LENGTH)
+            .map_err(Error::truncated)?;
 
         let r#type = buffer.read_u8();
         if r#type != CMD_TYPE {
@@ -53,7 +53,9 @@ impl Ws6in1Serde for Ws6in1DataHeader {
     where
         Self: Sized,
     {
-        buffer.check_remaining(Self::LENGTH)?;
+        buffer
+            .check_remaining(Self::LENGTH)
+            .map_err(Error::truncated)?;
 
         let item_cnt = buffer.read_u16::<BigEndian>();
         let item_idx = buffer.read_u16::<BigEndian>();
@@ -102,7 +104,9 @@ impl<V: Ws6in1Container<u8>> Ws6in1Serde for Ws6in1PayloadBase<V> {
     where
         Self: Sized,
     {
-        buffer.check_remaining(Self::LENGTH)?;
+        buffer
+            .check_remaining(Self::LENGTH)
+            .map_err(Error::truncated)?;
 
         let len = buffer.read_u8() as usize;
         let mut payload = [0; Ws6in1PayloadBase::<()>::MAX_PAYLOAD_LEN];
@@ -158,7 +162,9 @@ impl<V: Ws6in1Container<u8>> Ws6in1Serde for Ws6in1DataFrameBase<V> {
     where
         Self: Sized,
     {
-        buffer.check_remaining(Self::LENGTH)?;
+        buffer
+            .check_remaining(Self::LENGTH)
+            .map_err(Error::truncated)?;
 
         let r#type = buffer.read_u8();
         if r#type != Self::FRAME_TYPE {
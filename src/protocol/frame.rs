@@ -49,7 +49,9 @@ impl Ws6in1Footer {
     }
 
     pub fn deserialize(buffer: &mut Cursor<&[u8]>) -> Result<Self> {
-        buffer.check_remaining(Self::LENGTH)?;
+        buffer
+            .check_remaining(Self::LENGTH)
+            .map_err(Error::truncated)?;
 
         let _crc = buffer.read_u16::<BigEndian>();
         let magic = buffer.read_u8();
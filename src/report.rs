@@ -0,0 +1,288 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+//! Human readable rendering of readings.
+
+use core::fmt::{Display, Formatter, Result};
+
+use time::OffsetDateTime;
+
+use crate::{
+    parser::{Ws6in1ChannelConfig, Ws6in1Data},
+    units::{PrecipitationUnit, PressureUnit, UnitSystem, Ws6in1Units},
+};
+
+/// Renders a [Ws6in1Data] as aligned multi-line report or as compact
+/// single line. Created by [Ws6in1Data::report].
+#[derive(Clone, Copy, Debug)]
+pub struct Ws6in1Report<'a> {
+    data: &'a Ws6in1Data,
+    units: UnitSystem,
+    compact: bool,
+    channels: Option<&'a Ws6in1ChannelConfig<'a>>,
+}
+
+impl Ws6in1Data {
+    /// Returns a renderer for this reading with metric units.
+    pub fn report(&self) -> Ws6in1Report<'_> {
+        Ws6in1Report {
+            data: self,
+            units: UnitSystem::default(),
+            compact: false,
+            channels: None,
+        }
+    }
+}
+
+impl<'a> Ws6in1Report<'a> {
+    const LABEL_WIDTH: usize = 12;
+    const MISSING: &'static str = "---";
+
+    /// Renders all quantities in the given unit system.
+    pub fn units(mut self, units: UnitSystem) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Renders everything into a single line if set.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Uses channel names and calibration from the given configuration.
+    pub fn channels(mut self, channels: &'a Ws6in1ChannelConfig<'a>) -> Self {
+        self.channels = Some(channels);
+        self
+    }
+
+    fn fmt_time(&self, f: &mut Formatter) -> Result {
+        match OffsetDateTime::from_unix_timestamp(self.data.local_timestamp) {
+            Ok(time) => write!(
+                f,
+                "{:04}-{:02}-{:02} {:02}:{:02}",
+                time.year(),
+                u8::from(time.month()),
+                time.day(),
+                time.hour(),
+                time.minute(),
+            ),
+            Err(_) => f.write_str(Self::MISSING),
+        }
+    }
+
+    fn fmt_label(&self, f: &mut Formatter, label: &str) -> Result {
+        if self.compact {
+            write!(f, " | {label} ")
+        } else {
+            // Keep at least one space after long labels.
+            write!(f, "\n{label:<width$} ", width = Self::LABEL_WIDTH - 1)
+        }
+    }
+
+    fn fmt_channel_label(&self, f: &mut Formatter, idx: usize) -> Result {
+        let name = self
+            .channels
+            .and_then(|x| x.channel(idx))
+            .and_then(|x| x.name);
+        match (name, self.compact) {
+            (Some(name), _) => self.fmt_label(f, name),
+            (None, true) => write!(f, " | ch{} ", idx + 1),
+            (None, false) => write!(
+                f,
+                "\nChannel {:<width$}",
+                idx + 1,
+                width = Self::LABEL_WIDTH - "Channel ".len()
+            ),
+        }
+    }
+}
+
+fn pressure_precision(units: &Ws6in1Units) -> usize {
+    match units.pressure {
+        PressureUnit::InchesOfMercury => 2,
+        PressureUnit::Hectopascal => 1,
+        PressureUnit::MillimetersOfMercury => 0,
+    }
+}
+
+fn precipitation_precision(units: &Ws6in1Units) -> usize {
+    match units.precipitation {
+        PrecipitationUnit::Millimeters => 1,
+        PrecipitationUnit::Inches => 2,
+    }
+}
+
+impl Display for Ws6in1Report<'_> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let mut data = self.data.clone();
+        if let Some(channels) = self.channels {
+            for (idx, ext) in data.ext.iter_mut().enumerate() {
+                if let (Some(ext), Some(channel)) = (ext, channels.channel(idx))
+                {
+                    *ext = channel.calibrate(ext);
+                }
+            }
+        }
+        let data = self.units.convert(&data);
+        let units = &data.units;
+        let pressure = pressure_precision(units);
+        let rain = precipitation_precision(units);
+
+        if !self.compact {
+            write!(f, "{:<width$}", "Time", width = Self::LABEL_WIDTH)?;
+        }
+        self.fmt_time(f)?;
+
+        let indoor = &data.indoor;
+        self.fmt_label(f, "Indoor")?;
+        write!(f, "{:.1} {}", indoor.temperature, indoor.humidity)?;
+        self.fmt_label(f, "Pressure")?;
+        write!(
+            f,
+            "{:.*} (abs {:.*})",
+            pressure, indoor.baro_sea, pressure, indoor.baro_absolute
+        )?;
+
+        self.fmt_label(f, "Outdoor")?;
+        match &data.outdoor {
+            Some(outdoor) => {
+                write!(
+                    f,
+                    "{:.1} {} dew point {:.1}",
+                    outdoor.temperature, outdoor.humidity, outdoor.dew_point
+                )?;
+                self.fmt_label(f, "Wind")?;
+                write!(
+                    f,
+                    "{:.1} gust {:.1} from {} ({})",
+                    outdoor.wind_actual,
+                    outdoor.wind_gust,
+                    outdoor.wind_dir.compass(),
+                    outdoor.wind_dir,
+                )?;
+                self.fmt_label(f, "Rain")?;
+                write!(
+                    f,
+                    "{:.*} today, {:.*} actual",
+                    rain, outdoor.rain_day, rain, outdoor.rain_actual
+                )?;
+                self.fmt_label(f, "UV index")?;
+                write!(f, "{:.1}", outdoor.uv_index.0)?;
+            }
+            None => f.write_str(Self::MISSING)?,
+        }
+
+        for (idx, ext) in data.ext.iter().enumerate() {
+            self.fmt_channel_label(f, idx)?;
+            match ext {
+                Some(ext) => {
+                    write!(f, "{:.1} {}", ext.temperature, ext.humidity)?
+                }
+                None => f.write_str(Self::MISSING)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parser::{Ws6in1Channel, Ws6in1OutdoorData},
+        units::{Celsius, Degrees, MetersPerSecond, Millimeters, Percent},
+    };
+
+    fn data() -> Ws6in1Data {
+        Ws6in1Data::builder()
+            .local_timestamp(1579282200)
+            .indoor_climate(Celsius(20.4), Percent(49))
+            .outdoor(Ws6in1OutdoorData {
+                temperature: Celsius(6.0),
+                humidity: Percent(60),
+                rain_day: Millimeters(1.2),
+                wind_actual: MetersPerSecond(2.0),
+                wind_gust: MetersPerSecond(4.0),
+                wind_dir: Degrees(129),
+                dew_point: Celsius(-1.2),
+                ..Default::default()
+            })
+            .ext_climate(0, Celsius(27.3), Percent(57))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_report() {
+        let mut channels = Ws6in1ChannelConfig::default();
+        channels.channels[0] = Ws6in1Channel {
+            name: Some("Greenhouse"),
+            ..Default::default()
+        };
+
+        let data = data();
+        let report = format!("{}", data.report().channels(&channels));
+        assert_eq!(
+            "Time        2020-01-17 17:30\n\
+            Indoor      20.4°C 49%\n\
+            Pressure    1013.2 hPa (abs 1013.2 hPa)\n\
+            Outdoor     6.0°C 60% dew point -1.2°C\n\
+            Wind        2.0 m/s gust 4.0 m/s from SE (129°)\n\
+            Rain        1.2 mm today, 0.0 mm actual\n\
+            UV index    0.0\n\
+            Greenhouse  27.3°C 57%\n\
+            Channel 2   ---\n\
+            Channel 3   ---\n\
+            Channel 4   ---\n\
+            Channel 5   ---\n\
+            Channel 6   ---\n\
+            Channel 7   ---",
+            report
+        );
+    }
+
+    #[test]
+    fn test_long_channel_name() {
+        let mut channels = Ws6in1ChannelConfig::default();
+        channels.channels[0] = Ws6in1Channel {
+            name: Some("Greenhouse A1"),
+            ..Default::default()
+        };
+
+        let data = data();
+        let report = format!("{}", data.report().channels(&channels));
+        assert!(report.contains("\nGreenhouse A1 27.3°C 57%\n"));
+    }
+
+    #[test]
+    fn test_compact_report() {
+        let mut data = data();
+        data.outdoor = None;
+        let report =
+            format!("{}", data.report().units(UnitSystem::Us).compact(true));
+        assert_eq!(
+            "2020-01-17 17:30 | Indoor 68.7°F 49% \
+            | Pressure 29.92 inHg (abs 29.92 inHg) | Outdoor --- \
+            | ch1 81.1°F 57% | ch2 --- | ch3 --- | ch4 --- | ch5 --- \
+            | ch6 --- | ch7 ---",
+            report
+        );
+    }
+}
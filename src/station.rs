@@ -0,0 +1,204 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+//! Static metadata of a weather station.
+
+use time::{OffsetDateTime, UtcOffset};
+#[cfg(feature = "tz")]
+use time_tz::{OffsetDateTimeExt, Tz};
+
+use crate::{
+    parser::{DstPolicy, Ws6in1ChannelConfig, Ws6in1Data},
+    units::Meters,
+    Error, Result,
+};
+
+/// Timezone the console clock runs in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StationTimezone {
+    /// Fixed UTC offset without daylight saving time.
+    Fixed(UtcOffset),
+    /// IANA timezone from the tz database.
+    #[cfg(feature = "tz")]
+    Named(&'static Tz),
+}
+
+impl StationTimezone {
+    /// Converts the console time of a reading into UTC assuming the
+    /// console clock runs in this timezone. `policy` only applies to
+    /// named timezones.
+    #[cfg_attr(not(feature = "tz"), allow(unused_variables))]
+    pub fn utc_datetime(
+        &self,
+        data: &Ws6in1Data,
+        policy: DstPolicy,
+    ) -> Result<OffsetDateTime> {
+        match self {
+            Self::Fixed(offset) => data.utc_datetime(*offset),
+            #[cfg(feature = "tz")]
+            Self::Named(tz) => data.utc_datetime_tz(*tz, policy),
+        }
+    }
+
+    /// Converts a time into the local time of this timezone.
+    pub fn local_datetime(&self, datetime: OffsetDateTime) -> OffsetDateTime {
+        match self {
+            Self::Fixed(offset) => datetime.to_offset(*offset),
+            #[cfg(feature = "tz")]
+            Self::Named(tz) => datetime.to_timezone(*tz),
+        }
+    }
+}
+
+impl Default for StationTimezone {
+    fn default() -> Self {
+        Self::Fixed(UtcOffset::UTC)
+    }
+}
+
+/// Location, identity and configuration of a weather station.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StationInfo<'a> {
+    /// Display name of the station.
+    pub name: Option<&'a str>,
+    /// Latitude in degrees, positive north of the equator.
+    latitude: f32,
+    /// Longitude in degrees, positive east of Greenwich.
+    longitude: f32,
+    /// Altitude of the barometer above mean sea level.
+    pub altitude: Meters,
+    /// Timezone of the console clock.
+    pub timezone: StationTimezone,
+    /// Console model, e.g. "CC8488".
+    pub console_model: Option<&'a str>,
+    /// Names and calibration of the extra sensor channels.
+    pub channels: Ws6in1ChannelConfig<'a>,
+}
+
+impl<'a> StationInfo<'a> {
+    /// Creates station metadata at the given location.
+    /// Returns [Error::InvalidCoordinates] for out of range coordinates.
+    pub fn new(
+        latitude: f32,
+        longitude: f32,
+        altitude: Meters,
+    ) -> Result<Self> {
+        if !(-90.0..=90.0).contains(&latitude)
+            || !(-180.0..=180.0).contains(&longitude)
+        {
+            return Err(Error::InvalidCoordinates {
+                latitude,
+                longitude,
+            });
+        }
+
+        Ok(Self {
+            latitude,
+            longitude,
+            altitude,
+            ..Default::default()
+        })
+    }
+
+    /// Sets the station name.
+    pub fn with_name(mut self, name: &'a str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Sets the timezone of the console clock.
+    pub fn with_timezone(mut self, timezone: StationTimezone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Sets the console model.
+    pub fn with_console_model(mut self, model: &'a str) -> Self {
+        self.console_model = Some(model);
+        self
+    }
+
+    /// Sets the extra sensor channel configuration.
+    pub fn with_channels(mut self, channels: Ws6in1ChannelConfig<'a>) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Returns the latitude in degrees, positive north of the equator.
+    pub fn latitude(&self) -> f32 {
+        self.latitude
+    }
+
+    /// Returns the longitude in degrees, positive east of Greenwich.
+    pub fn longitude(&self) -> f32 {
+        self.longitude
+    }
+
+    /// Returns true if the station is located on the northern hemisphere.
+    pub fn is_northern_hemisphere(&self) -> bool {
+        self.latitude >= 0.0
+    }
+
+    /// Converts the console time of a reading into UTC using the
+    /// station timezone. `policy` only applies to named timezones.
+    pub fn utc_datetime(
+        &self,
+        data: &Ws6in1Data,
+        policy: DstPolicy,
+    ) -> Result<OffsetDateTime> {
+        self.timezone.utc_datetime(data, policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{datetime, offset};
+
+    use super::*;
+
+    #[test]
+    fn test_station_info() {
+        let station = StationInfo::new(48.14, 11.58, Meters(519.0))
+            .unwrap()
+            .with_name("Rooftop")
+            .with_timezone(StationTimezone::Fixed(offset!(+1)));
+        assert!(station.is_northern_hemisphere());
+        assert_eq!(48.14, station.latitude());
+        assert_eq!(11.58, station.longitude());
+        assert_eq!(Some("Rooftop"), station.name);
+
+        let data = Ws6in1Data::builder()
+            .local_datetime(datetime!(2020-01-17 17:30))
+            .build()
+            .unwrap();
+        assert_eq!(
+            datetime!(2020-01-17 16:30 UTC),
+            station.utc_datetime(&data, DstPolicy::Reject).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_coordinates() {
+        assert!(matches!(
+            StationInfo::new(91.0, 0.0, Meters(0.0)),
+            Err(Error::InvalidCoordinates { .. })
+        ));
+        assert!(StationInfo::new(-33.9, -181.0, Meters(0.0)).is_err());
+        assert!(StationInfo::new(f32::NAN, 0.0, Meters(0.0)).is_err());
+    }
+}
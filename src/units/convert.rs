@@ -0,0 +1,300 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use core::fmt::{Debug, Display, Formatter, Result};
+
+use super::{
+    Celsius, Fahrenheit, Feet, Hectopascal, Inches, InchesOfMercury, Kelvin,
+    KilometersPerHour, Knots, Meters, MetersPerSecond, MilesPerHour,
+    Millimeters, MillimetersOfMercury,
+};
+
+/// Interface to a family of units of the same physical quantity.
+pub trait Unit: Copy + Debug + Default + PartialEq {
+    /// Quantity type used as canonical representation of this unit family.
+    type Canonical: Copy;
+
+    /// Returns the symbol of this unit.
+    fn symbol(self) -> &'static str;
+    /// Converts a canonical quantity into a value of this unit.
+    fn convert(self, value: Self::Canonical) -> f32;
+    /// Converts a value of this unit into the canonical quantity.
+    fn to_canonical(self, value: f32) -> Self::Canonical;
+    /// Formats a value of this unit including its symbol.
+    fn fmt_value(self, value: f32, f: &mut Formatter) -> Result;
+}
+
+/// A value together with its unit.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Measurement<U> {
+    /// Numerical value in the given unit.
+    pub value: f32,
+    /// Unit of the value.
+    pub unit: U,
+}
+
+impl<U: Unit> Measurement<U> {
+    /// Converts this measurement into the canonical quantity.
+    pub fn to_canonical(self) -> U::Canonical {
+        self.unit.to_canonical(self.value)
+    }
+
+    /// Converts this measurement into another unit of the same family.
+    pub fn convert(self, unit: U) -> Self {
+        Self {
+            value: unit.convert(self.to_canonical()),
+            unit,
+        }
+    }
+}
+
+impl<U: Unit> Display for Measurement<U> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        self.unit.fmt_value(self.value, f)
+    }
+}
+
+/// Implements [From] in both directions between a canonical quantity
+/// and another unit of the same family.
+macro_rules! conversion {
+    ($canonical:ident <=> $other:ident, |$x:ident| $to:expr, |$y:ident| $from:expr) => {
+        impl From<$canonical> for $other {
+            fn from(value: $canonical) -> Self {
+                let $x = value.0;
+                Self($to)
+            }
+        }
+
+        impl From<$other> for $canonical {
+            fn from(value: $other) -> Self {
+                let $y = value.0;
+                Self($from)
+            }
+        }
+    };
+}
+
+/// Implements [From] in both directions between two non canonical units
+/// by converting through the canonical quantity.
+macro_rules! indirect_conversion {
+    ($a:ident <=> $b:ident via $canonical:ident) => {
+        impl From<$a> for $b {
+            fn from(value: $a) -> Self {
+                $canonical::from(value).into()
+            }
+        }
+
+        impl From<$b> for $a {
+            fn from(value: $b) -> Self {
+                $canonical::from(value).into()
+            }
+        }
+    };
+}
+
+/// Declares an enum selecting one unit of a unit family.
+macro_rules! unit_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident: $canonical:ident { $($variant:ident),+ $(,)? }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::$canonical
+            }
+        }
+
+        impl Unit for $name {
+            type Canonical = $canonical;
+
+            fn symbol(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $variant::SYMBOL),+
+                }
+            }
+
+            fn convert(self, value: $canonical) -> f32 {
+                match self {
+                    $(Self::$variant => $variant::from(value).0),+
+                }
+            }
+
+            fn to_canonical(self, value: f32) -> $canonical {
+                match self {
+                    $(Self::$variant => $canonical::from($variant(value))),+
+                }
+            }
+
+            fn fmt_value(self, value: f32, f: &mut Formatter) -> Result {
+                match self {
+                    $(Self::$variant => Display::fmt(&$variant(value), f)),+
+                }
+            }
+        }
+
+        impl $canonical {
+            /// Converts this quantity into the given unit.
+            pub fn to_unit(self, unit: $name) -> Measurement<$name> {
+                Measurement {
+                    value: unit.convert(self),
+                    unit,
+                }
+            }
+        }
+    };
+}
+
+conversion!(Celsius <=> Fahrenheit, |c| c * 1.8 + 32.0, |f| (f - 32.0) / 1.8);
+conversion!(Celsius <=> Kelvin, |c| c + 273.15, |k| k - 273.15);
+indirect_conversion!(Fahrenheit <=> Kelvin via Celsius);
+
+/// Hectopascal per inch of mercury at 0 °C.
+const HPA_PER_INHG: f32 = 33.863_89;
+/// Hectopascal per millimeter of mercury at 0 °C.
+const HPA_PER_MMHG: f32 = 1.333_224;
+
+conversion!(
+    Hectopascal <=> InchesOfMercury,
+    |p| p / HPA_PER_INHG,
+    |p| p * HPA_PER_INHG
+);
+conversion!(
+    Hectopascal <=> MillimetersOfMercury,
+    |p| p / HPA_PER_MMHG,
+    |p| p * HPA_PER_MMHG
+);
+indirect_conversion!(InchesOfMercury <=> MillimetersOfMercury via Hectopascal);
+
+/// Meters per second in one statute mile per hour.
+const MPS_PER_MPH: f32 = 0.447_04;
+/// Meters per second in one knot.
+const MPS_PER_KNOT: f32 = 1852.0 / 3600.0;
+
+conversion!(
+    MetersPerSecond <=> KilometersPerHour,
+    |v| v * 3.6,
+    |v| v / 3.6
+);
+conversion!(
+    MetersPerSecond <=> MilesPerHour,
+    |v| v / MPS_PER_MPH,
+    |v| v * MPS_PER_MPH
+);
+conversion!(
+    MetersPerSecond <=> Knots,
+    |v| v / MPS_PER_KNOT,
+    |v| v * MPS_PER_KNOT
+);
+indirect_conversion!(KilometersPerHour <=> MilesPerHour via MetersPerSecond);
+indirect_conversion!(KilometersPerHour <=> Knots via MetersPerSecond);
+indirect_conversion!(MilesPerHour <=> Knots via MetersPerSecond);
+
+conversion!(Millimeters <=> Inches, |h| h / 25.4, |h| h * 25.4);
+conversion!(Meters <=> Feet, |h| h / 0.3048, |h| h * 0.3048);
+
+unit_enum!(
+    /// Supported temperature units.
+    TemperatureUnit: Celsius { Celsius, Fahrenheit, Kelvin }
+);
+unit_enum!(
+    /// Supported air pressure units.
+    PressureUnit: Hectopascal {
+        Hectopascal,
+        InchesOfMercury,
+        MillimetersOfMercury,
+    }
+);
+unit_enum!(
+    /// Supported wind speed units.
+    SpeedUnit: MetersPerSecond {
+        MetersPerSecond,
+        KilometersPerHour,
+        MilesPerHour,
+        Knots,
+    }
+);
+unit_enum!(
+    /// Supported precipitation units.
+    PrecipitationUnit: Millimeters { Millimeters, Inches }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < expected.abs() * 1e-4 + 1e-4,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_temperature_conversion() {
+        assert_close(68.0, Fahrenheit::from(Celsius(20.0)).0);
+        assert_close(293.15, Kelvin::from(Celsius(20.0)).0);
+        assert_close(-40.0, Celsius::from(Fahrenheit(-40.0)).0);
+        assert_close(273.15, Kelvin::from(Fahrenheit(32.0)).0);
+    }
+
+    #[test]
+    fn test_pressure_conversion() {
+        assert_close(29.921, InchesOfMercury::from(Hectopascal(1013.25)).0);
+        assert_close(760.0, MillimetersOfMercury::from(Hectopascal(1013.25)).0);
+        assert_close(
+            760.0,
+            MillimetersOfMercury::from(InchesOfMercury(29.921)).0,
+        );
+    }
+
+    #[test]
+    fn test_speed_conversion() {
+        assert_close(36.0, KilometersPerHour::from(MetersPerSecond(10.0)).0);
+        assert_close(22.369, MilesPerHour::from(MetersPerSecond(10.0)).0);
+        assert_close(19.438, Knots::from(MetersPerSecond(10.0)).0);
+        assert_close(1.852, KilometersPerHour::from(Knots(1.0)).0);
+    }
+
+    #[test]
+    fn test_length_conversion() {
+        assert_close(1000.0, Feet::from(Meters(304.8)).0);
+        assert_close(0.3048, Meters::from(Feet(1.0)).0);
+    }
+
+    #[test]
+    fn test_unit_enum_conversion() {
+        let m = Millimeters(25.4).to_unit(PrecipitationUnit::Inches);
+        assert_close(1.0, m.value);
+        assert_close(25.4, m.to_canonical().0);
+        assert_eq!("1 in", format!("{m}"));
+
+        let t = Measurement {
+            value: 212.0,
+            unit: TemperatureUnit::Fahrenheit,
+        }
+        .convert(TemperatureUnit::Celsius);
+        assert_close(100.0, t.value);
+        assert_eq!("°C", t.unit.symbol());
+    }
+}
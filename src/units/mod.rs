@@ -0,0 +1,233 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+//! Strongly typed physical quantities as reported by the console.
+//!
+//! Every quantity is a thin newtype around the raw number so that values
+//! with different units cannot be mixed up accidentally. The parser reports
+//! all values in the canonical metric types [Celsius], [Hectopascal],
+//! [MetersPerSecond] and [Millimeters]. Conversions to other units are
+//! provided through [From] implementations and the [Unit] enums, whole
+//! readings can be converted with [UnitSystem].
+
+use core::{
+    fmt::{Display, Formatter, Result},
+    ops::Add,
+};
+
+mod convert;
+mod system;
+
+pub use convert::{
+    Measurement, PrecipitationUnit, PressureUnit, SpeedUnit, TemperatureUnit,
+    Unit,
+};
+pub use system::{
+    UnitSystem, Ws6in1ConvertedData, Ws6in1ConvertedExtData,
+    Ws6in1ConvertedIndoorData, Ws6in1ConvertedOutdoorData, Ws6in1Units,
+};
+
+macro_rules! quantity {
+    (
+        $(#[$meta:meta])*
+        $name:ident($inner:ty), $symbol:literal, $sep:literal
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+        pub struct $name(pub $inner);
+
+        impl $name {
+            /// Symbol of the unit of this quantity.
+            pub const SYMBOL: &'static str = $symbol;
+
+            /// Returns the raw value without unit information.
+            pub const fn value(self) -> $inner {
+                self.0
+            }
+        }
+
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> Result {
+                Display::fmt(&self.0, f)?;
+                f.write_str($sep)?;
+                f.write_str(Self::SYMBOL)
+            }
+        }
+    };
+}
+
+quantity!(
+    /// Temperature in degrees Celsius.
+    Celsius(f32), "°C", ""
+);
+quantity!(
+    /// Temperature in degrees Fahrenheit.
+    Fahrenheit(f32), "°F", ""
+);
+quantity!(
+    /// Absolute temperature in Kelvin.
+    Kelvin(f32), "K", " "
+);
+quantity!(
+    /// Difference between two temperatures in Kelvin, which equals the
+    /// difference in degrees Celsius.
+    TemperatureDifference(f32), "K", " "
+);
+quantity!(
+    /// Relative humidity in percent.
+    Percent(u8), "%", ""
+);
+quantity!(
+    /// Air pressure in hectopascal (millibar).
+    Hectopascal(f32), "hPa", " "
+);
+quantity!(
+    /// Air pressure in inches of mercury.
+    InchesOfMercury(f32), "inHg", " "
+);
+quantity!(
+    /// Air pressure in millimeters of mercury.
+    MillimetersOfMercury(f32), "mmHg", " "
+);
+quantity!(
+    /// Wind speed in meters per second.
+    MetersPerSecond(f32), "m/s", " "
+);
+quantity!(
+    /// Wind speed in kilometers per hour.
+    KilometersPerHour(f32), "km/h", " "
+);
+quantity!(
+    /// Wind speed in statute miles per hour.
+    MilesPerHour(f32), "mph", " "
+);
+quantity!(
+    /// Wind speed in nautical miles per hour.
+    Knots(f32), "kn", " "
+);
+quantity!(
+    /// Precipitation height in millimeters.
+    Millimeters(f32), "mm", " "
+);
+quantity!(
+    /// Precipitation height in inches.
+    Inches(f32), "in", " "
+);
+quantity!(
+    /// Precipitation rate in millimeters per hour.
+    MillimetersPerHour(f32), "mm/h", " "
+);
+quantity!(
+    /// Length or altitude in meters.
+    Meters(f32), "m", " "
+);
+quantity!(
+    /// Length or altitude in international feet.
+    Feet(f32), "ft", " "
+);
+quantity!(
+    /// Absolute humidity in grams of water vapour per cubic meter of air.
+    GramsPerCubicMeter(f32), "g/m³", " "
+);
+quantity!(
+    /// Compass direction in degrees clockwise from north.
+    Degrees(u16), "°", ""
+);
+quantity!(
+    /// Dimensionless UV index according to WHO.
+    UvIndex(f32), "UVI", " "
+);
+
+impl TemperatureDifference {
+    /// Creates a temperature difference from a difference in degrees
+    /// Fahrenheit.
+    pub fn from_fahrenheit(value: f32) -> Self {
+        Self(value * 5.0 / 9.0)
+    }
+
+    /// Returns the difference in degrees Fahrenheit.
+    pub fn fahrenheit(self) -> f32 {
+        self.0 * 9.0 / 5.0
+    }
+}
+
+impl Add<TemperatureDifference> for Celsius {
+    type Output = Self;
+
+    fn add(self, rhs: TemperatureDifference) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Degrees {
+    const COMPASS_POINTS: [&'static str; 16] = [
+        "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW",
+        "WSW", "W", "WNW", "NW", "NNW",
+    ];
+
+    /// Returns the index of the nearest of the 16 compass points,
+    /// counted clockwise from north.
+    pub fn sector(self) -> usize {
+        ((usize::from(self.0 % 360) * 4 + 45) / 90) % 16
+    }
+
+    /// Returns the nearest point of the 16 point compass rose, e.g. "NNE".
+    pub fn compass(self) -> &'static str {
+        Self::COMPASS_POINTS[self.sector()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_quantities() {
+        assert_eq!("20.4°C", format!("{}", Celsius(20.4)));
+        assert_eq!("49%", format!("{}", Percent(49)));
+        assert_eq!("1017.0 hPa", format!("{:.1}", Hectopascal(1017.0)));
+        assert_eq!("3.5 m/s", format!("{}", MetersPerSecond(3.5)));
+        assert_eq!("129°", format!("{}", Degrees(129)));
+        assert_eq!("-0.5 K", format!("{}", TemperatureDifference(-0.5)));
+    }
+
+    #[test]
+    fn test_temperature_difference() {
+        let offset = TemperatureDifference::from_fahrenheit(-0.9);
+        assert!((offset.0 + 0.5).abs() < 1e-6);
+        assert!((offset.fahrenheit() + 0.9).abs() < 1e-6);
+        assert_eq!(Celsius(20.0), Celsius(20.5) + TemperatureDifference(-0.5));
+    }
+
+    #[test]
+    fn test_compass_points() {
+        assert_eq!("N", Degrees(0).compass());
+        assert_eq!("NNW", Degrees(348).compass());
+        assert_eq!("N", Degrees(349).compass());
+        assert_eq!("NNE", Degrees(12).compass());
+        assert_eq!("SE", Degrees(129).compass());
+        assert_eq!("W", Degrees(270).compass());
+        assert_eq!("N", Degrees(360).compass());
+    }
+}
@@ -0,0 +1,228 @@
+/******************************************************************************\
+    ws6in1-proto - A protocol library for CC8488 compatible weather stations
+    Copyright (C) 2025 Max Maisel
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+\******************************************************************************/
+
+use super::{
+    Degrees, Measurement, Percent, PrecipitationUnit, PressureUnit, SpeedUnit,
+    TemperatureUnit, UvIndex,
+};
+use crate::parser::{
+    Ws6in1Data, Ws6in1ExtData, Ws6in1IndoorData, Ws6in1OutdoorData,
+};
+
+/// A set of units used for every quantity of a reading.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Ws6in1Units {
+    pub temperature: TemperatureUnit,
+    pub pressure: PressureUnit,
+    pub speed: SpeedUnit,
+    pub precipitation: PrecipitationUnit,
+}
+
+/// Commonly used unit systems.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum UnitSystem {
+    /// °C, hPa, m/s and mm as reported by the console.
+    #[default]
+    Metric,
+    /// °F, inHg, mph and in.
+    Us,
+    /// Mixed British units: °C, hPa, mph and mm.
+    Uk,
+    /// User defined set of units.
+    Custom(Ws6in1Units),
+}
+
+impl UnitSystem {
+    /// Returns the units used by this unit system.
+    pub fn units(self) -> Ws6in1Units {
+        match self {
+            Self::Metric => Ws6in1Units::default(),
+            Self::Us => Ws6in1Units {
+                temperature: TemperatureUnit::Fahrenheit,
+                pressure: PressureUnit::InchesOfMercury,
+                speed: SpeedUnit::MilesPerHour,
+                precipitation: PrecipitationUnit::Inches,
+            },
+            Self::Uk => Ws6in1Units {
+                temperature: TemperatureUnit::Celsius,
+                pressure: PressureUnit::Hectopascal,
+                speed: SpeedUnit::MilesPerHour,
+                precipitation: PrecipitationUnit::Millimeters,
+            },
+            Self::Custom(units) => units,
+        }
+    }
+
+    /// Converts a complete reading into this unit system.
+    pub fn convert(self, data: &Ws6in1Data) -> Ws6in1ConvertedData {
+        let units = self.units();
+
+        Ws6in1ConvertedData {
+            local_timestamp: data.local_timestamp,
+            units,
+            indoor: Ws6in1ConvertedIndoorData::new(&data.indoor, &units),
+            outdoor: data
+                .outdoor
+                .as_ref()
+                .map(|x| Ws6in1ConvertedOutdoorData::new(x, &units)),
+            ext: data
+                .ext
+                .map(|x| x.map(|x| Ws6in1ConvertedExtData::new(&x, &units))),
+        }
+    }
+}
+
+/// [Ws6in1IndoorData] converted into a [UnitSystem].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Ws6in1ConvertedIndoorData {
+    pub temperature: Measurement<TemperatureUnit>,
+    pub humidity: Percent,
+    pub baro_sea: Measurement<PressureUnit>,
+    pub baro_absolute: Measurement<PressureUnit>,
+}
+
+impl Ws6in1ConvertedIndoorData {
+    fn new(data: &Ws6in1IndoorData, units: &Ws6in1Units) -> Self {
+        Self {
+            temperature: data.temperature.to_unit(units.temperature),
+            humidity: data.humidity,
+            baro_sea: data.baro_sea.to_unit(units.pressure),
+            baro_absolute: data.baro_absolute.to_unit(units.pressure),
+        }
+    }
+}
+
+/// [Ws6in1OutdoorData] converted into a [UnitSystem].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Ws6in1ConvertedOutdoorData {
+    pub temperature: Measurement<TemperatureUnit>,
+    pub humidity: Percent,
+    pub rain_day: Measurement<PrecipitationUnit>,
+    pub rain_actual: Measurement<PrecipitationUnit>,
+    pub wind_actual: Measurement<SpeedUnit>,
+    pub wind_gust: Measurement<SpeedUnit>,
+    pub wind_dir: Degrees,
+    pub uv_index: UvIndex,
+    pub dew_point: Measurement<TemperatureUnit>,
+}
+
+impl Ws6in1ConvertedOutdoorData {
+    fn new(data: &Ws6in1OutdoorData, units: &Ws6in1Units) -> Self {
+        Self {
+            temperature: data.temperature.to_unit(units.temperature),
+            humidity: data.humidity,
+            rain_day: data.rain_day.to_unit(units.precipitation),
+            rain_actual: data.rain_actual.to_unit(units.precipitation),
+            wind_actual: data.wind_actual.to_unit(units.speed),
+            wind_gust: data.wind_gust.to_unit(units.speed),
+            wind_dir: data.wind_dir,
+            uv_index: data.uv_index,
+            dew_point: data.dew_point.to_unit(units.temperature),
+        }
+    }
+}
+
+/// [Ws6in1ExtData] converted into a [UnitSystem].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Ws6in1ConvertedExtData {
+    pub temperature: Measurement<TemperatureUnit>,
+    pub humidity: Percent,
+}
+
+impl Ws6in1ConvertedExtData {
+    fn new(data: &Ws6in1ExtData, units: &Ws6in1Units) -> Self {
+        Self {
+            temperature: data.temperature.to_unit(units.temperature),
+            humidity: data.humidity,
+        }
+    }
+}
+
+/// [Ws6in1Data] converted into a [UnitSystem].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Ws6in1ConvertedData {
+    /// Unix timestamp in local timezone and with 60 seconds resolution.
+    pub local_timestamp: i64,
+    /// Units used by all measurements of this reading.
+    pub units: Ws6in1Units,
+    pub indoor: Ws6in1ConvertedIndoorData,
+    pub outdoor: Option<Ws6in1ConvertedOutdoorData>,
+    pub ext: [Option<Ws6in1ConvertedExtData>; Ws6in1Data::EXT_SENSOR_COUNT],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parser::Ws6in1Quality,
+        units::{Celsius, Hectopascal, MetersPerSecond, Millimeters, Unit},
+    };
+
+    #[test]
+    fn test_convert_to_us_units() {
+        let data = Ws6in1Data {
+            local_timestamp: 1579282200,
+            indoor: Ws6in1IndoorData {
+                temperature: Celsius(20.0),
+                humidity: Percent(49),
+                baro_sea: Hectopascal(1013.25),
+                baro_absolute: Hectopascal(954.0),
+            },
+            outdoor: Some(Ws6in1OutdoorData {
+                temperature: Celsius(0.0),
+                humidity: Percent(60),
+                rain_day: Millimeters(25.4),
+                wind_actual: MetersPerSecond(10.0),
+                ..Default::default()
+            }),
+            ext: [None; Ws6in1Data::EXT_SENSOR_COUNT],
+            console_units: Ws6in1Units::default(),
+            received: None,
+            quality: Ws6in1Quality::default(),
+        };
+
+        let converted = UnitSystem::Us.convert(&data);
+        let outdoor = converted.outdoor.unwrap();
+
+        assert_eq!(1579282200, converted.local_timestamp);
+        assert_eq!("°F", converted.indoor.temperature.unit.symbol());
+        assert!((converted.indoor.temperature.value - 68.0).abs() < 1e-3);
+        assert!((converted.indoor.baro_sea.value - 29.921).abs() < 1e-3);
+        assert!((outdoor.temperature.value - 32.0).abs() < 1e-3);
+        assert!((outdoor.rain_day.value - 1.0).abs() < 1e-3);
+        assert!((outdoor.wind_actual.value - 22.369).abs() < 1e-3);
+        assert_eq!(Percent(60), outdoor.humidity);
+    }
+
+    #[test]
+    fn test_metric_is_identity() {
+        let data = Ws6in1Data {
+            indoor: Ws6in1IndoorData {
+                temperature: Celsius(21.5),
+                baro_sea: Hectopascal(1017.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let converted = UnitSystem::Metric.convert(&data);
+        assert_eq!(21.5, converted.indoor.temperature.value);
+        assert_eq!(1017.0, converted.indoor.baro_sea.value);
+        assert!(converted.outdoor.is_none());
+    }
+}